@@ -0,0 +1,206 @@
+use {
+  super::*,
+  std::io::Cursor,
+  zip::{write::SimpleFileOptions, ZipArchive, ZipWriter},
+};
+
+impl Submission {
+  /// Zip the contents of `dir` into `additional_files`, base64 encoded as
+  /// judge0 expects.
+  ///
+  /// ```rust
+  /// use judge0_rs::Submission;
+  ///
+  /// let mut submission = Submission::default();
+  ///
+  /// submission.set_additional_files_from_dir("fixtures").unwrap();
+  /// ```
+  pub fn set_additional_files_from_dir(
+    &mut self,
+    dir: impl AsRef<Path>,
+  ) -> Result {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+
+    add_dir(&mut writer, dir.as_ref(), "")?;
+
+    self.additional_files =
+      Some(STANDARD.encode(writer.finish()?.into_inner()));
+
+    Ok(())
+  }
+
+  /// Decode the zip archive in `additional_files`, e.g. from a submission
+  /// fetched with `SubmissionField::AdditionalFiles`, and extract it into
+  /// `dest_dir`. Does nothing if there are no additional files.
+  ///
+  /// Fails with `Error::UnsafeArchivePath` before writing anything if an
+  /// entry would be extracted outside of `dest_dir`.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config, SubmissionField};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let submission = client
+  ///   .get_submission("a", Some(&[SubmissionField::AdditionalFiles]))
+  ///   .await
+  ///   .unwrap();
+  ///
+  /// submission.extract_additional_files("out").unwrap();
+  /// ```
+  pub fn extract_additional_files(&self, dest_dir: impl AsRef<Path>) -> Result {
+    let Some(additional_files) = &self.additional_files else {
+      return Ok(());
+    };
+
+    let archive = STANDARD
+      .decode(
+        additional_files
+          .chars()
+          .filter(|c| !c.is_ascii_whitespace())
+          .collect::<String>(),
+      )
+      .map_err(|_| Error::Base64("additional_files".into()))?;
+
+    let mut archive = ZipArchive::new(Cursor::new(archive))?;
+
+    for index in 0..archive.len() {
+      let file = archive.by_index(index)?;
+
+      if file.enclosed_name().is_none() {
+        return Err(Error::UnsafeArchivePath(file.name()?.to_string()));
+      }
+    }
+
+    for index in 0..archive.len() {
+      let mut file = archive.by_index(index)?;
+
+      let path = dest_dir.as_ref().join(
+        file
+          .enclosed_name()
+          .ok_or_else(|| Error::UnsafeArchivePath(index.to_string()))?,
+      );
+
+      if file.is_dir() {
+        fs::create_dir_all(&path)?;
+        continue;
+      }
+
+      if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+      }
+
+      io::copy(&mut file, &mut fs::File::create(&path)?)?;
+    }
+
+    Ok(())
+  }
+}
+
+/// Add the contents of `dir` to the archive under `prefix`, recursively and
+/// in file name order.
+fn add_dir(
+  writer: &mut ZipWriter<Cursor<Vec<u8>>>,
+  dir: &Path,
+  prefix: &str,
+) -> Result {
+  let mut entries = fs::read_dir(dir)?
+    .map(|entry| entry.map(|entry| entry.path()))
+    .collect::<Result<Vec<PathBuf>, io::Error>>()?;
+
+  entries.sort();
+
+  for path in entries {
+    let name = format!(
+      "{prefix}{}",
+      path.file_name().unwrap_or_default().to_string_lossy()
+    );
+
+    if path.is_dir() {
+      writer.add_directory(format!("{name}/"), SimpleFileOptions::default())?;
+      add_dir(writer, &path, &format!("{name}/"))?;
+    } else {
+      writer.start_file(name, SimpleFileOptions::default())?;
+      io::copy(&mut fs::File::open(&path)?, writer)?;
+    }
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir()
+      .join(format!("judge0-rs-{name}-{}", std::process::id()));
+
+    let _ = fs::remove_dir_all(&dir);
+
+    fs::create_dir_all(&dir).unwrap();
+
+    dir
+  }
+
+  #[test]
+  fn round_trip() {
+    let src = temp_dir("additional-files-src");
+
+    fs::write(src.join("run"), "python3 main.py\n").unwrap();
+    fs::create_dir(src.join("data")).unwrap();
+    fs::write(src.join("data/input.bin"), [0, 159, 146, 150]).unwrap();
+
+    let mut submission = Submission::default();
+
+    submission.set_additional_files_from_dir(&src).unwrap();
+
+    let dest = temp_dir("additional-files-dest");
+
+    submission.extract_additional_files(&dest).unwrap();
+
+    assert_eq!(
+      fs::read_to_string(dest.join("run")).unwrap(),
+      "python3 main.py\n"
+    );
+    assert_eq!(
+      fs::read(dest.join("data/input.bin")).unwrap(),
+      [0, 159, 146, 150]
+    );
+
+    fs::remove_dir_all(&src).unwrap();
+    fs::remove_dir_all(&dest).unwrap();
+  }
+
+  #[test]
+  fn path_traversal() {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+
+    writer
+      .start_file("ok.txt", SimpleFileOptions::default())
+      .unwrap();
+
+    writer
+      .start_file("../evil.txt", SimpleFileOptions::default())
+      .unwrap();
+
+    let submission = Submission {
+      additional_files: Some(
+        STANDARD.encode(writer.finish().unwrap().into_inner()),
+      ),
+      ..Default::default()
+    };
+
+    let dest = temp_dir("additional-files-traversal");
+
+    assert!(matches!(
+      submission.extract_additional_files(dest.join("out")),
+      Err(Error::UnsafeArchivePath(name)) if name == "../evil.txt"
+    ));
+
+    assert!(!dest.join("evil.txt").exists());
+    assert!(!dest.join("out/ok.txt").exists());
+
+    fs::remove_dir_all(&dest).unwrap();
+  }
+}
@@ -0,0 +1,272 @@
+use super::*;
+
+/// Builder for a `Client` with connection settings that don't belong in
+/// `Config`, such as custom headers and a proxy.
+///
+/// ```rust
+/// use {
+///   judge0_rs::{Client, Config},
+///   std::time::Duration,
+/// };
+///
+/// let client = Client::builder()
+///   .base_url("http://localhost:2358")
+///   .config(Config {
+///     authentication_token: Some("token".into()),
+///     ..Default::default()
+///   })
+///   .header("X-Request-Source", "grader")
+///   .proxy("http://proxy.internal:3128")
+///   .timeout(Duration::from_secs(30))
+///   .connect_timeout(Duration::from_secs(5))
+///   .build()
+///   .unwrap();
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ClientBuilder {
+  base_url: Option<String>,
+  config: Config,
+  connect_timeout: Option<Duration>,
+  headers: Vec<(String, String)>,
+  proxy: Option<String>,
+  timeout: Option<Duration>,
+}
+
+impl ClientBuilder {
+  /// The base URL of the judge0 instance, which must be set.
+  pub fn base_url(self, base_url: &str) -> Self {
+    Self {
+      base_url: Some(base_url.to_owned()),
+      ..self
+    }
+  }
+
+  /// The client configuration, replacing any set so far.
+  pub fn config(self, config: Config) -> Self {
+    Self { config, ..self }
+  }
+
+  /// A header to send with every request, in addition to the authentication
+  /// headers from the configuration.
+  pub fn header(mut self, name: &str, value: &str) -> Self {
+    self.headers.push((name.to_owned(), value.to_owned()));
+    self
+  }
+
+  /// A proxy to send all requests through.
+  pub fn proxy(self, url: &str) -> Self {
+    Self {
+      proxy: Some(url.to_owned()),
+      ..self
+    }
+  }
+
+  /// How long a request may take in total before it fails.
+  pub fn timeout(self, timeout: Duration) -> Self {
+    Self {
+      timeout: Some(timeout),
+      ..self
+    }
+  }
+
+  /// How long to wait for a connection to be established, overriding
+  /// `Config::connect_timeout` regardless of the order the two are set in.
+  pub fn connect_timeout(self, connect_timeout: Duration) -> Self {
+    Self {
+      connect_timeout: Some(connect_timeout),
+      ..self
+    }
+  }
+
+  /// Build the client, failing with `Error::MissingBaseUrl` if no base URL
+  /// was set, or if a header, the proxy URL or `Config::root_cert_pem` is
+  /// invalid.
+  pub fn build(self) -> Result<Client> {
+    let base_url = self.base_url.clone().ok_or(Error::MissingBaseUrl)?;
+
+    let config = Config {
+      connect_timeout: self.connect_timeout.or(self.config.connect_timeout),
+      ..self.config.clone()
+    };
+
+    let client = self.http_client(&config)?;
+
+    Ok(Client::from_parts(&base_url, client, self, config))
+  }
+
+  /// Build the underlying HTTP client with this builder's headers, proxy and
+  /// timeout, and the connection settings from `config`. Also used when the
+  /// client is reconfigured, so these settings survive `Client::configure`.
+  pub(crate) fn http_client(&self, config: &Config) -> Result<reqwest::Client> {
+    let mut headers = HeaderMap::new();
+
+    for (name, value) in &self.headers {
+      headers.insert(
+        HeaderName::from_str(name)
+          .map_err(|_| Error::HeaderName(name.clone()))?,
+        HeaderValue::from_str(value)
+          .map_err(|_| Error::HeaderValue(value.clone()))?,
+      );
+    }
+
+    let mut builder = http_client_builder(config)?.default_headers(headers);
+
+    if let Some(connect_timeout) = self.connect_timeout {
+      builder = builder.connect_timeout(connect_timeout);
+    }
+
+    if let Some(proxy) = &self.proxy {
+      builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+    }
+
+    if let Some(timeout) = self.timeout {
+      builder = builder.timeout(timeout);
+    }
+
+    Ok(builder.build()?)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, mockito::Server};
+
+  fn local() -> ClientBuilder {
+    Client::builder().base_url("http://localhost:2358")
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn build_ok() {
+    let mut server = Server::new_async().await;
+
+    let client = Client::builder()
+      .base_url("http://judge0.invalid")
+      .connect_timeout(Duration::from_secs(1))
+      .config(Config {
+        authentication_token: Some("token".into()),
+        ..Default::default()
+      })
+      .header("X-Request-Source", "grader")
+      .proxy(&server.url())
+      .timeout(Duration::from_secs(5))
+      .build()
+      .unwrap();
+
+    assert_eq!(client.config.connect_timeout, Some(Duration::from_secs(1)));
+
+    let mock = server
+      .mock("GET", "/languages")
+      .match_header("x-request-source", "grader")
+      .match_header("x-auth-token", "token")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(r#"[{ "id": 45, "name": "Assembly (NASM 2.14.02)" }]"#)
+      .create();
+
+    assert_eq!(client.get_languages().await.unwrap().len(), 1);
+
+    mock.assert();
+  }
+
+  #[test]
+  fn build_invalid_header() {
+    assert!(matches!(
+      local().header("X-Request-Source", "a\nb").build(),
+      Err(Error::HeaderValue(_))
+    ));
+
+    assert!(matches!(
+      local().header("not a header", "value").build(),
+      Err(Error::HeaderName(_))
+    ));
+  }
+
+  const TEST_CA: &str = "-----BEGIN CERTIFICATE-----
+MIIBjjCCATWgAwIBAgIUdZbaWakh3EY0j5rlOelIHyqlpvYwCgYIKoZIzj0EAwIw
+HDEaMBgGA1UEAwwRanVkZ2UwLXJzIHRlc3QgQ0EwIBcNMjYxMDE2MDA0NzQ3WhgP
+MjEyNjA5MjIwMDQ3NDdaMBwxGjAYBgNVBAMMEWp1ZGdlMC1ycyB0ZXN0IENBMFkw
+EwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAERGkPE1i3VQAscIcYrUlx5VfVXz9EI85m
+IJtK57CSLj7aKw5hw2zvGGQ7Y6pi4hNkT9fgmSWDCS+Kmbn0W9u+paNTMFEwHQYD
+VR0OBBYEFL90oN6rFRzUZQSUACwOscywWZrsMB8GA1UdIwQYMBaAFL90oN6rFRzU
+ZQSUACwOscywWZrsMA8GA1UdEwEB/wQFMAMBAf8wCgYIKoZIzj0EAwIDRwAwRAIg
+dikR5RHYSN0moLD3z8neY5w+kKAq8Vmui/0D4U5t5rgCIBHQAcKSviTAadMszC6a
+8bc5LyoWLEM1A6mVtvWWWMiZ
+-----END CERTIFICATE-----
+";
+
+  #[test]
+  fn build_root_cert() {
+    let client = local()
+      .config(Config {
+        root_cert_pem: Some(TEST_CA.into()),
+        ..Default::default()
+      })
+      .build()
+      .unwrap();
+
+    assert!(client.config.root_cert_pem.is_some());
+
+    assert!(matches!(
+      local()
+        .config(Config {
+          root_cert_pem: Some(b"not a certificate".to_vec()),
+          ..Default::default()
+        })
+        .build(),
+      Err(Error::Certificate(_))
+    ));
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn configure_keeps_connection_settings() {
+    let mut server = Server::new_async().await;
+
+    let client = Client::builder()
+      .base_url(&server.url())
+      .header("X-Request-Source", "grader")
+      .build()
+      .unwrap()
+      .configure(Config {
+        authentication_token: Some("token".into()),
+        ..Default::default()
+      })
+      .unwrap();
+
+    let mock = server
+      .mock("GET", "/languages")
+      .match_header("x-request-source", "grader")
+      .match_header("x-auth-token", "token")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body("[]")
+      .create();
+
+    client.get_languages().await.unwrap();
+
+    mock.assert();
+
+    assert!(matches!(
+      client.configure(Config {
+        root_cert_pem: Some(b"not a certificate".to_vec()),
+        ..Default::default()
+      }),
+      Err(Error::Certificate(_))
+    ));
+  }
+
+  #[test]
+  fn build_missing_base_url() {
+    assert!(matches!(
+      Client::builder().build(),
+      Err(Error::MissingBaseUrl)
+    ));
+  }
+
+  #[test]
+  fn build_invalid_proxy() {
+    assert!(matches!(
+      local().proxy("not a url").build(),
+      Err(Error::Request(_))
+    ));
+  }
+}
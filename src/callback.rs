@@ -0,0 +1,804 @@
+use {
+  super::*,
+  crate::model::encode_query_component,
+  hmac::{Hmac, Mac},
+  sha2::Sha256,
+  std::net::SocketAddr,
+  tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    task::JoinSet,
+    time::timeout,
+  },
+};
+
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+
+const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The response a callback handler should send back to judge0.
+///
+/// Judge0 treats any 2xx response to its `PUT` request as a successful
+/// delivery, and retries the callback otherwise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallbackAck {
+  pub status: StatusCode,
+  pub body: String,
+}
+
+impl CallbackAck {
+  /// Acknowledge a callback with an empty `200 OK` response.
+  pub fn ok() -> Self {
+    Self {
+      status: StatusCode::OK,
+      body: String::new(),
+    }
+  }
+
+  /// Reject a callback, which judge0 will retry.
+  pub fn reject(status: StatusCode, body: &str) -> Self {
+    Self {
+      status,
+      body: body.to_owned(),
+    }
+  }
+
+  /// Whether judge0 will consider the callback delivered.
+  pub fn is_success(&self) -> bool {
+    self.status.is_success()
+  }
+
+  /// Render the acknowledgment as a raw HTTP/1.1 response.
+  pub fn to_http(&self) -> Vec<u8> {
+    format!(
+      "HTTP/1.1 {}\r\ncontent-length: {}\r\n\r\n{}",
+      self.status,
+      self.body.len(),
+      self.body
+    )
+    .into_bytes()
+  }
+}
+
+/// Parse the submission judge0 sends in the body of a callback request.
+///
+/// ```rust
+/// use judge0_rs::{parse_callback, CallbackAck};
+///
+/// let body = br#"{ "token": "a", "status": { "id": 3, "description": "Accepted" } }"#;
+///
+/// let ack = match parse_callback(body) {
+///   Ok(submission) => CallbackAck::ok(),
+///   Err(error) => CallbackAck::reject(http::StatusCode::BAD_REQUEST, &error.to_string()),
+/// };
+/// ```
+pub fn parse_callback(body: &[u8]) -> Result<Submission> {
+  Ok(serde_json::from_slice(body)?)
+}
+
+/// Check that a callback request's target, e.g. `/callback?secret=...`,
+/// carries the secret set with `Submission::set_callback_with_secret`.
+/// Callbacks without the right secret should be rejected as spoofed.
+///
+/// ```rust
+/// use judge0_rs::{verify_callback_secret, CallbackAck};
+///
+/// let ack = if verify_callback_secret("/callback?secret=s3cret", "s3cret") {
+///   CallbackAck::ok()
+/// } else {
+///   CallbackAck::reject(http::StatusCode::FORBIDDEN, "invalid secret")
+/// };
+/// ```
+pub fn verify_callback_secret(target: &str, secret: &str) -> bool {
+  let expected = encode_query_component(secret);
+
+  target
+    .split_once('?')
+    .map(|(_, query)| query.split('#').next().unwrap_or_default())
+    .into_iter()
+    .flat_map(|query| query.split('&'))
+    .filter_map(|pair| pair.split_once('='))
+    .filter(|(name, _)| *name == "secret")
+    .any(|(_, value)| {
+      value.len() == expected.len()
+        && value
+          .bytes()
+          .zip(expected.bytes())
+          .fold(0, |diff, (a, b)| diff | (a ^ b))
+          == 0
+    })
+}
+
+/// Check a callback body against an HMAC-SHA256 `signature` computed with
+/// the shared `secret`, for deployments that sign callbacks. The signature
+/// is hex encoded, optionally prefixed with `sha256=`, and compared in
+/// constant time.
+///
+/// ```rust
+/// use judge0_rs::{verify_hmac, CallbackAck};
+///
+/// let body = br#"{"token":"a"}"#;
+///
+/// let signature =
+///   "sha256=ccbea1c874615f091aeaf1279f3056fba7336b2042ba31dd459b6d0d607488be";
+///
+/// let ack = if verify_hmac(body, signature, "s3cret") {
+///   CallbackAck::ok()
+/// } else {
+///   CallbackAck::reject(http::StatusCode::FORBIDDEN, "invalid signature")
+/// };
+/// ```
+pub fn verify_hmac(body: &[u8], signature: &str, secret: &str) -> bool {
+  let signature = signature.trim();
+
+  let signature = signature.strip_prefix("sha256=").unwrap_or(signature);
+
+  if !signature.len().is_multiple_of(2) || !signature.is_ascii() {
+    return false;
+  }
+
+  let Ok(signature) = (0..signature.len())
+    .step_by(2)
+    .map(|i| u8::from_str_radix(&signature[i..i + 2], 16))
+    .collect::<Result<Vec<u8>, _>>()
+  else {
+    return false;
+  };
+
+  let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+    return false;
+  };
+
+  mac.update(body);
+
+  mac.verify_slice(&signature).is_ok()
+}
+
+/// Collects the callbacks judge0 sends for a batch of submissions created
+/// with `callback_url` set, and tells when every expected token has
+/// arrived, so batches can be awaited without polling.
+///
+/// ```rust
+/// use judge0_rs::{BatchCallbackCollector, CallbackAck};
+///
+/// let mut collector = BatchCallbackCollector::new(vec!["a".into(), "b".into()]);
+///
+/// let body = br#"{ "token": "a", "status": { "id": 3, "description": "Accepted" } }"#;
+///
+/// let ack = match collector.handle(body) {
+///   Ok(complete) => CallbackAck::ok(),
+///   Err(error) => CallbackAck::reject(http::StatusCode::BAD_REQUEST, &error.to_string()),
+/// };
+/// ```
+#[derive(Debug, Clone)]
+pub struct BatchCallbackCollector {
+  tokens: Vec<String>,
+  received: HashMap<String, Submission>,
+}
+
+impl BatchCallbackCollector {
+  /// Expect a callback for each of `tokens`.
+  pub fn new(tokens: Vec<String>) -> Self {
+    Self {
+      tokens,
+      received: HashMap::new(),
+    }
+  }
+
+  /// Record the submission in a callback body and return whether every
+  /// expected token has now arrived. Fails with `Error::Callback` if the
+  /// token isn't part of the batch. A repeated callback for the same token,
+  /// e.g. a retry, replaces the earlier one.
+  pub fn handle(&mut self, body: &[u8]) -> Result<bool> {
+    let submission = parse_callback(body)?;
+
+    let token = submission
+      .token
+      .clone()
+      .ok_or_else(|| Error::Callback("missing token".into()))?;
+
+    if !self.tokens.contains(&token) {
+      return Err(Error::Callback(format!("unexpected token: {token}")));
+    }
+
+    self.received.insert(token, submission);
+
+    Ok(self.is_complete())
+  }
+
+  /// Whether every expected token has arrived.
+  pub fn is_complete(&self) -> bool {
+    self
+      .tokens
+      .iter()
+      .all(|token| self.received.contains_key(token))
+  }
+
+  /// The tokens still waiting for a callback, in batch order.
+  pub fn pending(&self) -> Vec<&str> {
+    self
+      .tokens
+      .iter()
+      .filter(|token| !self.received.contains_key(*token))
+      .map(String::as_str)
+      .collect()
+  }
+
+  /// The submissions received so far, in batch order.
+  pub fn into_submissions(mut self) -> Vec<Submission> {
+    self
+      .tokens
+      .iter()
+      .filter_map(|token| self.received.remove(token))
+      .collect()
+  }
+}
+
+impl Client {
+  /// Create a submission and wait for judge0 to deliver the result to a
+  /// callback listener bound to `bind_addr`, instead of polling for it.
+  ///
+  /// The submission's `callback_url` is pointed at the listener, so the bound
+  /// address must be reachable from the judge0 instance, and an unspecified
+  /// address like `0.0.0.0` fails with `Error::Callback`. Connections that
+  /// aren't a valid callback are answered with an error and otherwise
+  /// ignored, and those that stall or send an oversized request are closed.
+  /// Gives up with `Error::Timeout` after `Config::poll_timeout`.
+  ///
+  /// Judge0 delivers the callback encoded the same way the submission was
+  /// created, so it's decoded when `Config::base64_encoded` or the
+  /// submission's `base64_encoded_override` is set.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config, Submission};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let submission = client
+  ///   .create_and_await_callback(
+  ///     Submission {
+  ///       source_code: "print(Hello, world)".into(),
+  ///       language_id: 1,
+  ///       ..Submission::default()
+  ///     },
+  ///     "172.17.0.1:8080".parse().unwrap(),
+  ///   )
+  ///   .await
+  ///   .unwrap();
+  /// ```
+  pub async fn create_and_await_callback(
+    &self,
+    submission: Submission,
+    bind_addr: SocketAddr,
+  ) -> Result<Submission> {
+    if bind_addr.ip().is_unspecified() {
+      return Err(Error::Callback(format!(
+        "cannot advertise unspecified address {bind_addr} as callback url"
+      )));
+    }
+
+    let base64_encoded = submission
+      .base64_encoded_override
+      .unwrap_or(self.config.base64_encoded);
+
+    let listener = TcpListener::bind(bind_addr).await?;
+
+    let callback_url = format!("http://{}/", listener.local_addr()?);
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+
+    // Aborting the accept task drops its join set, which aborts every
+    // connection that is still being read.
+    let accept = tokio::spawn(async move {
+      let mut connections = JoinSet::new();
+
+      while let Ok((mut stream, _)) = listener.accept().await {
+        let tx = tx.clone();
+
+        connections.spawn(async move {
+          if let Ok(submission) = receive(&mut stream).await {
+            let _ = tx.send(submission);
+          }
+        });
+      }
+    });
+
+    let result = async {
+      let result = self
+        .create_submission(Submission {
+          callback_url: Some(callback_url),
+          ..submission
+        })
+        .await?;
+
+      let token = result.token().to_owned();
+
+      let mut submission = timeout(self.config.poll_timeout, async {
+        while let Some(submission) = rx.recv().await {
+          if submission.token.as_deref() == Some(token.as_str()) {
+            return Ok(submission);
+          }
+        }
+
+        Err(Error::Callback("callback listener stopped".into()))
+      })
+      .await
+      .map_err(|_| Error::Timeout)??;
+
+      if base64_encoded {
+        submission.decode(self.config.base64_engine())?;
+      }
+
+      Ok(submission)
+    }
+    .await;
+
+    accept.abort();
+
+    result
+  }
+}
+
+/// Read a callback request from the stream, acknowledge it, and parse the
+/// submission it carries.
+async fn receive(stream: &mut TcpStream) -> Result<Submission> {
+  let (method, body) = read_request(stream).await?;
+
+  if method != "PUT" {
+    stream
+      .write_all(
+        &CallbackAck::reject(StatusCode::METHOD_NOT_ALLOWED, "").to_http(),
+      )
+      .await?;
+
+    return Err(Error::Callback(format!("unexpected method {method}")));
+  }
+
+  let submission = parse_callback(&body);
+
+  let ack = match &submission {
+    Ok(_) => CallbackAck::ok(),
+    Err(error) => {
+      CallbackAck::reject(StatusCode::BAD_REQUEST, &error.to_string())
+    }
+  };
+
+  stream.write_all(&ack.to_http()).await?;
+
+  submission
+}
+
+/// Read a chunk from the stream, giving up if nothing arrives within
+/// `READ_TIMEOUT`.
+async fn read_chunk(
+  stream: &mut TcpStream,
+  buffer: &mut Vec<u8>,
+) -> Result<usize> {
+  let mut chunk = [0; 1024];
+
+  let read = timeout(READ_TIMEOUT, stream.read(&mut chunk))
+    .await
+    .map_err(|_| Error::Callback("timed out reading request".into()))??;
+
+  buffer.extend_from_slice(&chunk[..read]);
+
+  Ok(read)
+}
+
+/// Read a single HTTP request from the stream, returning its method and body.
+/// Requests with headers over `MAX_HEADER_BYTES` or a body over
+/// `MAX_BODY_BYTES` are rejected.
+async fn read_request(stream: &mut TcpStream) -> Result<(String, Vec<u8>)> {
+  let mut buffer = Vec::new();
+
+  let header_end = loop {
+    if let Some(position) =
+      buffer.windows(4).position(|window| window == b"\r\n\r\n")
+    {
+      break position + 4;
+    }
+
+    if buffer.len() > MAX_HEADER_BYTES {
+      return Err(Error::Callback("request headers too large".into()));
+    }
+
+    if read_chunk(stream, &mut buffer).await? == 0 {
+      return Err(Error::Callback("connection closed before headers".into()));
+    }
+  };
+
+  let head = String::from_utf8_lossy(&buffer[..header_end]).into_owned();
+
+  let mut lines = head.lines();
+
+  let method = lines
+    .next()
+    .and_then(|line| line.split_whitespace().next())
+    .ok_or_else(|| Error::Callback("missing request line".into()))?
+    .to_owned();
+
+  let content_length = lines
+    .filter_map(|line| line.split_once(':'))
+    .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+    .map(|(_, value)| value.trim().parse::<usize>())
+    .transpose()
+    .map_err(|_| Error::Callback("invalid content-length".into()))?
+    .unwrap_or_default();
+
+  if content_length > MAX_BODY_BYTES {
+    return Err(Error::Callback("request body too large".into()));
+  }
+
+  let mut body = buffer.split_off(header_end);
+
+  while body.len() < content_length {
+    if read_chunk(stream, &mut body).await? == 0 {
+      return Err(Error::Callback("connection closed before body".into()));
+    }
+  }
+
+  body.truncate(content_length);
+
+  Ok((method, body))
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, mockito::Server};
+
+  #[test]
+  fn parse_callback_ok() {
+    let submission = parse_callback(
+      br#"{
+        "stdout": "hello, Judge0\n",
+        "time": "0.001",
+        "memory": 380,
+        "token": "eb0dd001-66db-47f4-8a69-b736c9bc23f6",
+        "status": { "id": 3, "description": "Accepted" }
+      }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+      submission.token.as_deref(),
+      Some("eb0dd001-66db-47f4-8a69-b736c9bc23f6")
+    );
+
+    assert_eq!(submission.status.unwrap().id, 3);
+  }
+
+  #[test]
+  fn parse_callback_invalid() {
+    assert!(matches!(parse_callback(b"not json"), Err(Error::Serde(_))));
+  }
+
+  #[test]
+  fn batch_callback_collector() {
+    let mut collector =
+      BatchCallbackCollector::new(vec!["a".into(), "b".into(), "c".into()]);
+
+    assert_eq!(collector.pending(), ["a", "b", "c"]);
+
+    assert!(!collector
+      .handle(br#"{ "token": "b", "stdout": "2" }"#)
+      .unwrap());
+
+    assert!(matches!(
+      collector.handle(br#"{ "token": "d", "stdout": "4" }"#),
+      Err(Error::Callback(_))
+    ));
+
+    assert!(matches!(
+      collector.handle(br#"{ "stdout": "1" }"#),
+      Err(Error::Callback(_))
+    ));
+
+    assert!(!collector
+      .handle(br#"{ "token": "a", "stdout": "1" }"#)
+      .unwrap());
+
+    assert_eq!(collector.pending(), ["c"]);
+
+    assert!(collector
+      .handle(br#"{ "token": "c", "stdout": "3" }"#)
+      .unwrap());
+
+    assert!(collector.is_complete());
+
+    assert_eq!(
+      collector
+        .into_submissions()
+        .into_iter()
+        .map(|submission| submission.stdout.unwrap())
+        .collect::<Vec<String>>(),
+      ["1", "2", "3"]
+    );
+  }
+
+  #[test]
+  fn verify_secret() {
+    assert!(verify_callback_secret("/callback?secret=abc", "abc"));
+    assert!(verify_callback_secret(
+      "/callback?job=1&secret=a%20b%26c",
+      "a b&c"
+    ));
+
+    assert!(!verify_callback_secret("/callback?secret=abd", "abc"));
+    assert!(!verify_callback_secret("/callback?secret=ab", "abc"));
+    assert!(!verify_callback_secret("/callback", "abc"));
+    assert!(!verify_callback_secret("/callback?other=abc", "abc"));
+  }
+
+  #[test]
+  fn verify_hmac_signature() {
+    let body = b"The quick brown fox jumps over the lazy dog";
+
+    let signature =
+      "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8";
+
+    assert!(verify_hmac(body, signature, "key"));
+    assert!(verify_hmac(body, &format!("sha256={signature}"), "key"));
+    assert!(verify_hmac(body, &signature.to_uppercase(), "key"));
+
+    assert!(!verify_hmac(
+      b"The quick brown fox jumps over the lazy cat",
+      signature,
+      "key"
+    ));
+    assert!(!verify_hmac(body, signature, "other"));
+    assert!(!verify_hmac(body, &signature[..62], "key"));
+    assert!(!verify_hmac(body, "not hex", "key"));
+  }
+
+  #[test]
+  fn ack() {
+    let ack = CallbackAck::ok();
+
+    assert!(ack.is_success());
+    assert_eq!(
+      ack.to_http(),
+      b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n"
+    );
+
+    let ack = CallbackAck::reject(StatusCode::BAD_REQUEST, "invalid");
+
+    assert!(!ack.is_success());
+
+    assert_eq!(
+      ack.to_http(),
+      b"HTTP/1.1 400 Bad Request\r\ncontent-length: 7\r\n\r\ninvalid"
+    );
+  }
+
+  async fn connect(addr: SocketAddr) -> TcpStream {
+    loop {
+      match TcpStream::connect(addr).await {
+        Ok(stream) => break stream,
+        Err(_) => tokio::task::yield_now().await,
+      }
+    }
+  }
+
+  async fn deliver(addr: SocketAddr, body: &str) -> String {
+    let mut stream = connect(addr).await;
+
+    stream
+      .write_all(
+        format!(
+          "PUT / HTTP/1.1\r\nHost: {addr}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+          body.len()
+        )
+        .as_bytes(),
+      )
+      .await
+      .unwrap();
+
+    let mut response = String::new();
+
+    stream.read_to_string(&mut response).await.unwrap();
+
+    response
+  }
+
+  async fn pair() -> (TcpStream, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+
+    let (client, server) = tokio::join!(
+      TcpStream::connect(listener.local_addr().unwrap()),
+      listener.accept()
+    );
+
+    (client.unwrap(), server.unwrap().0)
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn read_request_headers_too_large() {
+    let (mut client, mut server) = pair().await;
+
+    client
+      .write_all(&vec![b'a'; MAX_HEADER_BYTES + 1])
+      .await
+      .unwrap();
+
+    assert!(matches!(
+      read_request(&mut server).await,
+      Err(Error::Callback(message)) if message == "request headers too large"
+    ));
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn read_request_body_too_large() {
+    let (mut client, mut server) = pair().await;
+
+    client
+      .write_all(
+        format!(
+          "PUT / HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+          MAX_BODY_BYTES + 1
+        )
+        .as_bytes(),
+      )
+      .await
+      .unwrap();
+
+    assert!(matches!(
+      read_request(&mut server).await,
+      Err(Error::Callback(message)) if message == "request body too large"
+    ));
+  }
+
+  #[tokio::test(start_paused = true)]
+  async fn read_request_timeout() {
+    let (mut client, mut server) = pair().await;
+
+    client
+      .write_all(b"PUT / HTTP/1.1\r\nContent-Length: 2\r\n\r\n{")
+      .await
+      .unwrap();
+
+    assert!(matches!(
+      read_request(&mut server).await,
+      Err(Error::Callback(message)) if message == "timed out reading request"
+    ));
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn create_and_await_callback_ok() {
+    let mut server = Server::new_async().await;
+
+    let addr = std::net::TcpListener::bind("127.0.0.1:0")
+      .unwrap()
+      .local_addr()
+      .unwrap();
+
+    let mock = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .match_body(mockito::Matcher::PartialJsonString(format!(
+        r#"{{ "callback_url": "http://{addr}/" }}"#
+      )))
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "token": "d85cd024-1548-4165-96c7-7bc88673f194" }"#)
+      .create();
+
+    let client = Client::new(&server.url());
+
+    let callback = async {
+      let mut stalled = connect(addr).await;
+
+      stalled.write_all(b"PUT / HTTP/1.1\r\n").await.unwrap();
+
+      let mut stray = connect(addr).await;
+
+      stray
+        .write_all(b"GET / HTTP/1.1\r\nHost: example.com\r\n\r\n")
+        .await
+        .unwrap();
+
+      let mut stray_response = String::new();
+
+      stray.read_to_string(&mut stray_response).await.unwrap();
+
+      assert!(stray_response.starts_with("HTTP/1.1 405"));
+
+      deliver(
+        addr,
+        r#"{
+          "stdout": "hello, Judge0\n",
+          "token": "d85cd024-1548-4165-96c7-7bc88673f194",
+          "status": { "id": 3, "description": "Accepted" }
+        }"#,
+      )
+      .await
+    };
+
+    let (submission, response) = tokio::join!(
+      client.create_and_await_callback(
+        Submission {
+          source_code: r#"print("hello, Judge0")"#.into(),
+          language_id: 71,
+          ..Default::default()
+        },
+        addr,
+      ),
+      callback
+    );
+
+    let submission = submission.unwrap();
+
+    assert_eq!(submission.stdout.as_deref(), Some("hello, Judge0\n"));
+    assert_eq!(submission.status.unwrap().id, 3);
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn create_and_await_callback_base64() {
+    let mut server = Server::new_async().await;
+
+    let addr = std::net::TcpListener::bind("127.0.0.1:0")
+      .unwrap()
+      .local_addr()
+      .unwrap();
+
+    let mock = server
+      .mock("POST", "/submissions?base64_encoded=true&wait=false")
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "token": "d85cd024-1548-4165-96c7-7bc88673f194" }"#)
+      .create();
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        base64_encoded: true,
+        ..Default::default()
+      })
+      .unwrap();
+
+    let (submission, response) = tokio::join!(
+      client.create_and_await_callback(
+        Submission {
+          source_code: r#"print("hello, Judge0")"#.into(),
+          language_id: 71,
+          ..Default::default()
+        },
+        addr,
+      ),
+      deliver(
+        addr,
+        r#"{
+          "source_code": "cHJpbnQoImhlbGxvLCBKdWRnZTAiKQ==",
+          "stdout": "aGVsbG8sIEp1ZGdlMAo=",
+          "token": "d85cd024-1548-4165-96c7-7bc88673f194",
+          "status": { "id": 3, "description": "Accepted" }
+        }"#,
+      )
+    );
+
+    let submission = submission.unwrap();
+
+    assert_eq!(submission.source_code, r#"print("hello, Judge0")"#);
+    assert_eq!(submission.stdout.as_deref(), Some("hello, Judge0\n"));
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+
+    mock.assert();
+  }
+
+  #[tokio::test]
+  async fn create_and_await_callback_unspecified_address() {
+    let client = Client::new("http://judge0.invalid");
+
+    assert!(matches!(
+      client
+        .create_and_await_callback(
+          Submission::default(),
+          "0.0.0.0:0".parse().unwrap()
+        )
+        .await,
+      Err(Error::Callback(_))
+    ));
+  }
+}
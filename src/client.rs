@@ -1,10 +1,23 @@
 use super::*;
 
+const SERVER_VERSION_HEADER: &str = "X-Judge0-Version";
+
+const LIST_PAGE_SIZE: usize = 100;
+
+const MAX_ERROR_BODY_BYTES: usize = 4096;
+
+const MAX_SNIPPET_BYTES: usize = 256;
+
 #[derive(Debug)]
 pub struct Client {
   base_url: String,
   client: reqwest::Client,
-  config: Config,
+  connection: ClientBuilder,
+  pub(crate) config: Config,
+  config_info: Mutex<Option<ConfigInfo>>,
+  dedup_cache: Mutex<VecDeque<(u64, CreateResponse)>>,
+  language_map: Mutex<Option<HashMap<String, usize>>>,
+  server_version: Mutex<Option<String>>,
 }
 
 impl Client {
@@ -19,19 +32,64 @@ impl Client {
     Self {
       base_url: base_url.to_owned(),
       client: reqwest::Client::new(),
+      connection: ClientBuilder::default(),
       config: Config::default(),
+      config_info: Mutex::new(None),
+      dedup_cache: Mutex::new(VecDeque::new()),
+      language_map: Mutex::new(None),
+      server_version: Mutex::new(None),
+    }
+  }
+
+  /// Start building a client with custom headers, a proxy or timeouts.
+  ///
+  /// ```rust
+  /// use judge0_rs::Client;
+  ///
+  /// let client = Client::builder()
+  ///   .base_url("http://localhost:2358")
+  ///   .build()
+  ///   .unwrap();
+  /// ```
+  pub fn builder() -> ClientBuilder {
+    ClientBuilder::default()
+  }
+
+  pub(crate) fn from_parts(
+    base_url: &str,
+    client: reqwest::Client,
+    connection: ClientBuilder,
+    config: Config,
+  ) -> Self {
+    Self {
+      base_url: base_url.to_owned(),
+      client,
+      connection,
+      config,
+      config_info: Mutex::new(None),
+      dedup_cache: Mutex::new(VecDeque::new()),
+      language_map: Mutex::new(None),
+      server_version: Mutex::new(None),
     }
   }
 
   /// Configure the client.
   ///
+  /// This rebuilds the underlying HTTP client with the connection settings
+  /// from `config`, keeping any headers, proxy or timeout set with
+  /// `Client::builder`, and fails if `Config::root_cert_pem` is invalid.
+  ///
   /// ```rust
   /// use judge0_rs::{Client, Config};
   ///
-  /// let client = Client::new("http://localhost:2358").configure(Config::default());
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
   /// ```
-  pub fn configure(self, config: Config) -> Self {
-    Self { config, ..self }
+  pub fn configure(self, config: Config) -> Result<Self> {
+    Ok(Self {
+      client: self.connection.http_client(&config)?,
+      config,
+      ..self
+    })
   }
 
   /// Check if your authentication token is valid.
@@ -42,12 +100,12 @@ impl Client {
   /// let client = Client::new("http://localhost:2358").configure(Config {
   ///   authentication_token: Some("token".into()),
   ///   ..Default::default()
-  /// });
+  /// }).unwrap();
   ///
-  /// assert!(client.authenticate().await.is_ok());
+  /// assert!(client.authenticate().await.unwrap());
   /// ```
-  pub async fn authenticate(self) -> Result {
-    self.request("/authenticate", Method::POST).await
+  pub async fn authenticate(&self) -> Result<bool> {
+    self.verify("/authenticate").await
   }
 
   /// Check if your authorization token is valid.
@@ -58,12 +116,12 @@ impl Client {
   /// let client = Client::new("http://localhost:2358").configure(Config {
   ///   authorization_token: Some("token".into()),
   ///   ..Default::default()
-  /// });
+  /// }).unwrap();
   ///
-  /// assert!(client.authorize().await.is_ok());
+  /// assert!(client.authorize().await.unwrap());
   /// ```
-  pub async fn authorize(self) -> Result {
-    self.request("/authorize", Method::POST).await
+  pub async fn authorize(&self) -> Result<bool> {
+    self.verify("/authorize").await
   }
 
   /// Get active languages.
@@ -71,26 +129,63 @@ impl Client {
   /// ```rust
   /// use judge0_rs::{Client, Config};
   ///
-  /// let client = Client::new("http://localhost:2358").configure(Config::default());
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
   ///
   /// let languages = client.get_languages().await.unwrap();
   /// ```
-  pub async fn get_languages(self) -> Result<Vec<Language>> {
+  pub async fn get_languages(&self) -> Result<Vec<Language>> {
     self
       .request::<Vec<Language>>("/languages", Method::GET)
       .await
   }
 
+  /// Get a map from active language name to id, e.g. "Python (3.8.1)" to 71.
+  ///
+  /// The map is fetched once and cached for the lifetime of the client, so
+  /// it's cheap to call repeatedly.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let python = client.language_map().await.unwrap()["Python (3.8.1)"];
+  /// ```
+  pub async fn language_map(&self) -> Result<HashMap<String, usize>> {
+    if let Some(map) = self
+      .language_map
+      .lock()
+      .unwrap_or_else(PoisonError::into_inner)
+      .as_ref()
+    {
+      return Ok(map.clone());
+    }
+
+    let map = self
+      .get_languages()
+      .await?
+      .into_iter()
+      .map(|language| (language.name, language.id))
+      .collect::<HashMap<String, usize>>();
+
+    *self
+      .language_map
+      .lock()
+      .unwrap_or_else(PoisonError::into_inner) = Some(map.clone());
+
+    Ok(map)
+  }
+
   /// Get active and archived languages.
   ///
   /// ```rust
   /// use judge0_rs::{Client, Config};
   ///
-  /// let client = Client::new("http://localhost:2358").configure(Config::default());
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
   ///
   /// let languages = client.get_all_languages().await.unwrap();
   /// ```
-  pub async fn get_all_languages(self) -> Result<Vec<Language>> {
+  pub async fn get_all_languages(&self) -> Result<Vec<Language>> {
     self
       .request::<Vec<Language>>("/languages/all", Method::GET)
       .await
@@ -101,61 +196,280 @@ impl Client {
   /// ```rust
   /// use judge0_rs::{Client, Config};
   ///
-  /// let client = Client::new("http://localhost:2358").configure(Config::default());
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
   ///
   /// let language = client.get_language(1).await.unwrap();
   /// ```
-  pub async fn get_language(self, id: usize) -> Result<Language> {
+  pub async fn get_language(&self, id: usize) -> Result<Language> {
     self
       .request::<Language>(&format!("/languages/{id}"), Method::GET)
       .await
   }
 
+  /// Get several active languages by identifier concurrently, returning one
+  /// result per identifier in the same order.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let languages = client.get_languages_by_ids(&[45, 46]).await.unwrap();
+  /// ```
+  pub async fn get_languages_by_ids(
+    &self,
+    ids: &[usize],
+  ) -> Result<Vec<Result<Language>>> {
+    Ok(join_all(ids.iter().map(|id| self.get_language(*id))).await)
+  }
+
   /// Get all statuses.
   ///
   /// ```rust
   /// use judge0_rs::{Client, Config};
   ///
-  /// let client = Client::new("http://localhost:2358").configure(Config::default());
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
   ///
   /// let statuses = client.get_statuses().await.unwrap();
   /// ```
-  pub async fn get_statuses(self) -> Result<Vec<Status>> {
+  pub async fn get_statuses(&self) -> Result<Vec<Status>> {
     self.request::<Vec<Status>>("/statuses", Method::GET).await
   }
 
+  /// Get all statuses grouped by category.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config, StatusCategory};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let statuses = client.get_statuses_by_category().await.unwrap();
+  ///
+  /// let failures = &statuses[&StatusCategory::Failure];
+  /// ```
+  pub async fn get_statuses_by_category(
+    &self,
+  ) -> Result<HashMap<StatusCategory, Vec<Status>>> {
+    let mut categories = HashMap::<StatusCategory, Vec<Status>>::new();
+
+    for status in self.get_statuses().await? {
+      categories
+        .entry(status.category())
+        .or_default()
+        .push(status);
+    }
+
+    Ok(categories)
+  }
+
   /// Get about information.
   ///
   /// ```rust
   /// use judge0_rs::{Client, Config};
   ///
-  /// let client = Client::new("http://localhost:2358").configure(Config::default());
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
   ///
   /// let about = client.get_about().await.unwrap();
   /// ```
-  pub async fn get_about(self) -> Result<About> {
+  pub async fn get_about(&self) -> Result<About> {
     self.request::<About>("/about", Method::GET).await
   }
 
+  /// Check whether the instance's version, from `/about`, is in the range of
+  /// versions this crate supports, see `SUPPORTED_VERSIONS`.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let compatibility = client.check_compatibility().await.unwrap();
+  ///
+  /// if !compatibility.compatible {
+  ///   eprintln!("unsupported judge0 version {}", compatibility.server_version);
+  /// }
+  /// ```
+  pub async fn check_compatibility(&self) -> Result<Compatibility> {
+    Ok(Compatibility::new(&self.get_about().await?.version))
+  }
+
+  /// Get the configuration of the instance, including the limits submissions
+  /// must stay within.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let config_info = client.get_config_info().await.unwrap();
+  /// ```
+  pub async fn get_config_info(&self) -> Result<ConfigInfo> {
+    self
+      .request::<ConfigInfo>("/config_info", Method::GET)
+      .await
+  }
+
+  /// Get the features and limits supported by the instance, e.g. to check
+  /// whether `enable_network` is allowed before using it. The underlying
+  /// config info is fetched once and cached.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// if client.capabilities().await.unwrap().enable_wait {
+  ///   println!("waiting for submissions is supported");
+  /// }
+  /// ```
+  pub async fn capabilities(&self) -> Result<Capabilities> {
+    Ok(Capabilities::from(&self.cached_config_info().await?))
+  }
+
+  /// Get the config info of the instance, fetching it only the first time.
+  async fn cached_config_info(&self) -> Result<ConfigInfo> {
+    if let Some(config_info) = self
+      .config_info
+      .lock()
+      .unwrap_or_else(PoisonError::into_inner)
+      .as_ref()
+    {
+      return Ok(config_info.clone());
+    }
+
+    let config_info = self.get_config_info().await?;
+
+    *self
+      .config_info
+      .lock()
+      .unwrap_or_else(PoisonError::into_inner) = Some(config_info.clone());
+
+    Ok(config_info)
+  }
+
+  /// Measure the round trip time of a request for the cheap `/about`
+  /// endpoint, e.g. for health dashboards.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let latency = client.ping().await.unwrap();
+  /// ```
+  pub async fn ping(&self) -> Result<Duration> {
+    let start = Instant::now();
+
+    self.get_about().await?;
+
+    Ok(start.elapsed())
+  }
+
+  /// Verify at startup that the instance is reachable, that the configured
+  /// tokens are accepted and that `expected_language_id` is available.
+  ///
+  /// Every check runs, and all failures are reported together in
+  /// `Error::Preflight`, except when the instance can't be reached at all.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// client.preflight(71).await.unwrap();
+  /// ```
+  pub async fn preflight(&self, expected_language_id: usize) -> Result {
+    if let Err(error) = self.get_about().await {
+      return Err(Error::Preflight(vec![format!(
+        "instance is unreachable: {error}"
+      )]));
+    }
+
+    let mut failures = Vec::new();
+
+    if self.config.authentication_token.is_some() {
+      match self.authenticate().await {
+        Ok(true) => {}
+        Ok(false) => failures.push("authentication token was rejected".into()),
+        Err(error) => failures.push(format!("failed to authenticate: {error}")),
+      }
+    }
+
+    if self.config.authorization_token.is_some() {
+      match self.authorize().await {
+        Ok(true) => {}
+        Ok(false) => failures.push("authorization token was rejected".into()),
+        Err(error) => failures.push(format!("failed to authorize: {error}")),
+      }
+    }
+
+    if let Err(error) = self.get_language(expected_language_id).await {
+      failures.push(format!(
+        "language {expected_language_id} is unavailable: {error}"
+      ));
+    }
+
+    if failures.is_empty() {
+      Ok(())
+    } else {
+      Err(Error::Preflight(failures))
+    }
+  }
+
   /// Get worker information.
   ///
   /// ```rust
   /// use judge0_rs::{Client, Config};
   ///
-  /// let client = Client::new("http://localhost:2358").configure(Config::default());
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
   ///
   /// let workers = client.get_workers().await.unwrap();
   /// ```
-  pub async fn get_workers(self) -> Result<Vec<Worker>> {
+  pub async fn get_workers(&self) -> Result<Vec<Worker>> {
     self.request::<Vec<Worker>>("/workers", Method::GET).await
   }
 
+  /// Check whether processing is paused on the instance, that is whether
+  /// every worker on every queue is paused.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let paused = client.is_paused().await.unwrap();
+  /// ```
+  pub async fn is_paused(&self) -> Result<bool> {
+    let workers = self.get_workers().await?;
+
+    Ok(!workers.is_empty() && workers.iter().all(Worker::is_paused))
+  }
+
   /// Create a submission.
   ///
+  /// The submission's `wait_override` and `base64_encoded_override` take
+  /// precedence over the client configuration. When base64 encoding is
+  /// enabled, `source_code`, `stdin` and `expected_output` are encoded before
+  /// sending.
+  ///
+  /// Returns `CreateResponse::Completed` with the finished submission if the
+  /// instance waited for it, and `CreateResponse::Pending` with its token
+  /// otherwise. Fails with `Error::Api` if the instance rejected the
+  /// request, e.g. a 422 for an invalid field that `Error::is_validation`
+  /// recognizes, with `Error::Rejected` if it accepted the request but
+  /// reported an error for the submission or returned no token, and with
+  /// `Error::WaitNotAllowed` if waiting was requested but the instance has
+  /// it disabled.
+  ///
+  /// When `Config::dedup_cache_size` is set, creating a submission identical
+  /// to a recent one, in every field sent to the server and in whether it
+  /// waits and is base64 encoded, returns the cached result instead of
+  /// running it again.
+  ///
   /// ```rust
-  /// use judge0_rs::{Client, Config, Submission};
+  /// use judge0_rs::{Client, Config, CreateResponse, Submission};
   ///
-  /// let client = Client::new("http://localhost:2358").configure(Config::default());
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
   ///
   /// let submission = Submission {
   ///   source_code: "print(Hello, world)".into(),
@@ -163,581 +477,5625 @@ impl Client {
   ///   ..Submission::default()
   /// };
   ///
-  /// let result = client.create_submission(submission).await.unwrap();
+  /// match client.create_submission(submission).await.unwrap() {
+  ///   CreateResponse::Pending(token) => println!("queued as {}", token.token),
+  ///   CreateResponse::Completed(submission) => println!("{:?}", submission.stdout),
+  /// }
   /// ```
   pub async fn create_submission(
-    self,
+    &self,
     submission: Submission,
-  ) -> Result<Value> {
-    self
-      .request_with_body::<Value, Submission>(
-        &format!(
-          "/submissions?base64_encoded={}&wait={}",
-          self.config.base64_encoded, self.config.wait
-        ),
-        Method::POST,
-        submission,
-      )
-      .await
+  ) -> Result<CreateResponse> {
+    self.create(submission, true).await
   }
 
-  /// Get a single submission by token.
+  /// Create a submission as described for `create_submission`, only using
+  /// the dedup cache if `dedup` is set.
+  pub(crate) async fn create(
+    &self,
+    submission: Submission,
+    dedup: bool,
+  ) -> Result<CreateResponse> {
+    let submission = self.apply_defaults(submission)?;
+
+    let wait = submission.wait_override.unwrap_or(self.config.wait);
+
+    let base64_encoded = submission
+      .base64_encoded_override
+      .unwrap_or(self.config.base64_encoded);
+
+    let key = (dedup
+      && self.config.dedup_cache_size > 0
+      && submission.callback_url.is_none())
+    .then(|| submission.content_hash(wait, base64_encoded));
+
+    if let Some(key) = key {
+      if let Some((_, result)) = self
+        .dedup_cache
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .find(|(hash, _)| *hash == key)
+      {
+        return Ok(result.clone());
+      }
+    }
+
+    let (mut delay, mut retries) = (self.config.poll_interval, 0);
+
+    let result = loop {
+      match self.submit::<Value>(submission.clone(), wait).await {
+        Err(Error::QueueFull) if retries < self.config.queue_full_retries => {
+          sleep(delay).await;
+          delay *= 2;
+          retries += 1;
+        }
+        result => break result.map_err(wait_not_allowed)?,
+      }
+    };
+
+    if result["error"] == "wait not allowed" {
+      return Err(Error::WaitNotAllowed);
+    }
+
+    if !result["token"].is_string() || !result["error"].is_null() {
+      return Err(Error::Rejected(result));
+    }
+
+    let result = if ["status", "stdout"]
+      .iter()
+      .any(|field| !result[field].is_null())
+    {
+      let mut submission = serde_json::from_value::<Submission>(result)?;
+
+      if base64_encoded {
+        submission.decode(self.config.base64_engine())?;
+      }
+
+      CreateResponse::Completed(Box::new(submission))
+    } else {
+      CreateResponse::Pending(serde_json::from_value(result)?)
+    };
+
+    if let Some(key) = key {
+      let mut cache = self
+        .dedup_cache
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+
+      if cache.len() >= self.config.dedup_cache_size {
+        cache.pop_front();
+      }
+
+      cache.push_back((key, result.clone()));
+    }
+
+    Ok(result)
+  }
+
+  /// Create a submission without waiting for it, reading only the token from
+  /// the response.
+  ///
+  /// This is useful for callback-based workflows that never read the result
+  /// from the creation response. `Config::wait` and `wait_override` are
+  /// ignored.
   ///
   /// ```rust
   /// use judge0_rs::{Client, Config, Submission};
   ///
-  /// let client = Client::new("http://localhost:2358").configure(Config::default());
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
   ///
   /// let submission = Submission {
   ///   source_code: "print(Hello, world)".into(),
   ///   language_id: 1,
+  ///   callback_url: Some("https://example.com/callback".into()),
   ///   ..Submission::default()
   /// };
   ///
-  /// let result = client.create_submission(submission).await.unwrap();
-  ///
-  /// let submission = client.get_submission(result["token"], None).await.unwrap();
+  /// let token = client.create_submission_fire_and_forget(submission).await.unwrap();
   /// ```
-  pub async fn get_submission(
-    self,
-    token: &str,
-    fields: Option<&str>,
-  ) -> Result<Submission> {
+  pub async fn create_submission_fire_and_forget(
+    &self,
+    submission: Submission,
+  ) -> Result<Token> {
     self
-      .request::<Submission>(
-        &format!(
-          "/submissions/{token}?base64_encoded={}&wait={}&fields={}",
-          fields.unwrap_or("*"),
-          self.config.base64_encoded,
-          self.config.wait
-        ),
-        Method::GET,
-      )
+      .submit::<Token>(self.apply_defaults(submission)?, false)
       .await
   }
 
-  /// Delete a single submission by token.
+  /// Create a submission without waiting for it, switching to base64 encoding
+  /// for this request if any input field looks like it came from non-UTF-8
+  /// data.
+  ///
+  /// Rust strings are always valid UTF-8, so this checks for content that
+  /// judge0 commonly fails to convert: control characters such as NUL, and
+  /// the U+FFFD replacement character left behind by lossy conversion from
+  /// raw bytes. Base64 encoding is forced regardless of `Config::base64_encoded`
+  /// when any of `source_code`, `stdin` or `expected_output` contains them.
   ///
   /// ```rust
   /// use judge0_rs::{Client, Config, Submission};
   ///
-  /// let client = Client::new("http://localhost:2358").configure(Config::default());
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
   ///
   /// let submission = Submission {
-  ///   source_code: "print(Hello, world)".into(),
-  ///   language_id: 1,
+  ///   source_code: String::from_utf8_lossy(b"print(\"\xFE\")").into(),
+  ///   language_id: 71,
   ///   ..Submission::default()
   /// };
   ///
-  /// let result = client.create_submission(submission).await.unwrap();
-  ///
-  /// let submission = client.delete_submission(result["token"], None).await.unwrap();
+  /// let token = client.create_submission_safe(submission).await.unwrap();
   /// ```
-  pub async fn delete_submission(
-    self,
-    token: &str,
-    fields: Option<&str>,
-  ) -> Result<Submission> {
+  pub async fn create_submission_safe(
+    &self,
+    submission: Submission,
+  ) -> Result<Token> {
+    let submission = if submission.needs_base64() {
+      Submission {
+        base64_encoded_override: Some(true),
+        ..submission
+      }
+    } else {
+      submission
+    };
+
     self
-      .request::<Submission>(
-        &format!("/submissions/{token}?fields={}", fields.unwrap_or("*"),),
-        Method::DELETE,
-      )
+      .submit::<Token>(self.apply_defaults(submission)?, false)
       .await
   }
 
-  /// Create a batch submission.
+  /// Send a hand-crafted JSON body to the creation endpoint as is, with the
+  /// `base64_encoded` and `wait` query parameters from the configuration.
+  /// Useful for reproducing server bugs with bodies `Submission` can't
+  /// express. Fails with `Error::QueueFull` like `create_submission` when
+  /// the instance's queue is full.
   ///
   /// ```rust
-  /// use judge0_rs::{Client, Config, Submission};
-  ///
-  /// let client = Client::new("http://localhost:2358").configure(Config::default());
+  /// use {
+  ///   judge0_rs::{Client, Config},
+  ///   serde_json::json,
+  /// };
   ///
-  /// let submissions = vec![
-  ///   Submission {
-  ///     source_code: "print("foo")".into(),
-  ///     language_id: 1,
-  ///     ..Submission::default()
-  ///   },
-  ///   Submission {
-  ///     source_code: "print("bar")".into(),
-  ///     language_id: 1,
-  ///     ..Submission::default()
-  ///   },
-  ///   Submission {
-  ///     source_code: "print("baz")".into(),
-  ///     language_id: 1,
-  ///     ..Submission::default()
-  ///   },
-  /// ];
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
   ///
-  /// let result = client.batch_submit(submissions).await.unwrap();
+  /// let result = client
+  ///   .create_submission_raw(json!({
+  ///     "source_code": "print(1)",
+  ///     "language_id": "71",
+  ///   }))
+  ///   .await
+  ///   .unwrap();
   /// ```
-  pub async fn batch_submit(
-    self,
-    submissions: Vec<Submission>,
-  ) -> Result<Vec<Value>> {
+  pub async fn create_submission_raw(&self, body: Value) -> Result<Value> {
     self
-      .request_with_body::<Vec<Value>, Vec<Submission>>(
+      .request_with_body(
         &format!(
-          "/submissions/batch?base64_encoded={}",
-          self.config.base64_encoded
+          "/submissions?base64_encoded={}&wait={}",
+          self.config.base64_encoded, self.config.wait
         ),
         Method::POST,
-        submissions,
+        body,
       )
       .await
+      .map_err(queue_full)
   }
 
-  /// Get a batch submission.
+  /// Fill in the configured default language and compiler options a
+  /// submission leaves unset, and check it against `Config::max_input_bytes`.
+  fn apply_defaults(&self, mut submission: Submission) -> Result<Submission> {
+    if submission.language_id == 0 {
+      if let Some(language_id) = self.config.default_language_id {
+        submission.language_id = language_id;
+      }
+    }
+
+    if let Some(limit) = self.config.max_input_bytes {
+      let size = submission.source_code.len()
+        + submission.stdin.as_ref().map_or(0, String::len)
+        + submission.additional_files.as_ref().map_or(0, String::len);
+
+      if size > limit {
+        return Err(Error::InputTooLarge { size, limit });
+      }
+    }
+
+    if submission.compiler_options.is_none() {
+      submission.compiler_options = self
+        .config
+        .compiler_options
+        .get(&submission.language_id)
+        .cloned();
+    }
+
+    Ok(submission)
+  }
+
+  /// Send a submission, with defaults already applied, to the creation
+  /// endpoint, as JSON or as a multipart form depending on the configuration.
+  async fn submit<T: DeserializeOwned>(
+    &self,
+    submission: Submission,
+    wait: bool,
+  ) -> Result<T> {
+    let base64_encoded = submission
+      .base64_encoded_override
+      .unwrap_or(self.config.base64_encoded);
+
+    let endpoint =
+      format!("/submissions?base64_encoded={base64_encoded}&wait={wait}");
+
+    let body =
+      submission.request_body(base64_encoded, self.config.base64_engine())?;
+
+    let result = if self.config.use_multipart {
+      self
+        .request_with_form::<T, Value>(&endpoint, Method::POST, body)
+        .await
+    } else {
+      self
+        .request_with_body::<T, Value>(&endpoint, Method::POST, body)
+        .await
+    };
+
+    result.map_err(queue_full)
+  }
+
+  /// Get a single submission by token.
   ///
   /// ```rust
   /// use judge0_rs::{Client, Config, Submission};
   ///
-  /// let client = Client::new("http://localhost:2358").configure(Config::default());
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
   ///
-  /// let submissions = vec![
-  ///   Submission {
-  ///     source_code: "print("foo")".into(),
-  ///     language_id: 1,
-  ///     ..Submission::default()
-  ///   },
-  ///   Submission {
-  ///     source_code: "print("bar")".into(),
-  ///     language_id: 1,
-  ///     ..Submission::default()
-  ///   },
-  ///   Submission {
-  ///     source_code: "print("baz")".into(),
-  ///     language_id: 1,
-  ///     ..Submission::default()
-  ///   },
-  /// ];
+  /// let submission = Submission {
+  ///   source_code: "print(Hello, world)".into(),
+  ///   language_id: 1,
+  ///   ..Submission::default()
+  /// };
   ///
-  /// let result = client.batch_submit(submissions).await.unwrap();
+  /// let result = client.create_submission(submission).await.unwrap();
+  ///
+  /// let submission = client.get_submission(result.token(), None).await.unwrap();
+  /// ```
+  ///
+  /// Pass `Some(&[SubmissionField::Stdout, SubmissionField::Status])` to fetch
+  /// only those fields, or `None` for all of them.
+  ///
+  /// When only some fields are requested, every other field is `None` as if
+  /// the server had returned `null`. Use `Submission::was_fetched` to tell a
+  /// field that is genuinely empty from one that wasn't requested.
+  pub async fn get_submission(
+    &self,
+    token: &str,
+    fields: Option<&[SubmissionField]>,
+  ) -> Result<Submission> {
+    let mut submission = self
+      .request::<Submission>(
+        &format!(
+          "/submissions/{token}?base64_encoded={}&wait={}&fields={}",
+          self.config.base64_encoded,
+          self.config.wait,
+          SubmissionField::query(fields)
+        ),
+        Method::GET,
+      )
+      .await?;
+
+    self.fetched(&mut submission, fields)?;
+
+    Ok(submission)
+  }
+
+  /// Get a single submission by token with only the given fields, as a
+  /// `PartialSubmission` that refuses access to any other field.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config, SubmissionField};
   ///
-  /// let tokens = result
-  ///   .iter()
-  ///   .map(|value| value["token"])
-  ///   .collect::<Vec<String>>();
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
   ///
-  /// let batch_submission = get_back_submission(tokens, None).await.unwrap();
+  /// let submission = client
+  ///   .get_submission_partial("a", &[SubmissionField::Stdout])
+  ///   .await
+  ///   .unwrap();
   /// ```
-  pub async fn get_batch_submission(
-    self,
-    tokens: Vec<&str>,
-    fields: Option<&str>,
-  ) -> Result<Vec<Submission>> {
+  pub async fn get_submission_partial(
+    &self,
+    token: &str,
+    fields: &[SubmissionField],
+  ) -> Result<PartialSubmission> {
+    PartialSubmission::new(
+      &self.get_submission(token, Some(fields)).await?,
+      fields,
+    )
+  }
+
+  /// Get a single submission by token as the untyped JSON returned by the
+  /// server, which is useful for debugging or for fields `Submission`
+  /// doesn't model. Nothing is decoded, even if base64 encoding is enabled.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let json = client.get_submission_raw("a", None).await.unwrap();
+  ///
+  /// println!("{json:#}");
+  /// ```
+  pub async fn get_submission_raw(
+    &self,
+    token: &str,
+    fields: Option<&[SubmissionField]>,
+  ) -> Result<Value> {
     self
-      .request::<Vec<Submission>>(
+      .request::<Value>(
         &format!(
-          "/submission/batch?tokens={}&base64_encoded={}&fields={}",
-          tokens.join(","),
+          "/submissions/{token}?base64_encoded={}&wait={}&fields={}",
           self.config.base64_encoded,
-          fields.unwrap_or("*")
+          self.config.wait,
+          SubmissionField::query(fields)
         ),
         Method::GET,
       )
       .await
   }
 
-  /// Build pre-defined headers for each request.
-  fn headers(&self) -> Result<HeaderMap> {
-    let mut headers = HeaderMap::new();
-
-    let header_name = |name: &str| -> Result<HeaderName> {
-      HeaderName::from_str(name)
-        .map_err(|_| Error::HeaderName(name.to_string()))
-    };
-
-    let header_value = |value: &str| -> Result<HeaderValue> {
-      HeaderValue::from_str(value)
-        .map_err(|_| Error::HeaderName(value.to_string()))
-    };
+  /// Delete a single submission by token.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config, Submission};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let submission = Submission {
+  ///   source_code: "print(Hello, world)".into(),
+  ///   language_id: 1,
+  ///   ..Submission::default()
+  /// };
+  ///
+  /// let result = client.create_submission(submission).await.unwrap();
+  ///
+  /// let submission = client.delete_submission(result.token(), None).await.unwrap();
+  /// ```
+  pub async fn delete_submission(
+    &self,
+    token: &str,
+    fields: Option<&str>,
+  ) -> Result<Submission> {
+    self
+      .request::<Submission>(
+        &format!("/submissions/{token}?fields={}", fields.unwrap_or("*"),),
+        Method::DELETE,
+      )
+      .await
+  }
 
-    headers.insert("content-type", header_value("application/json")?);
+  /// List a page of submissions, optionally only those with the given status.
+  ///
+  /// The status filter is sent to the server as `status_id` for instances that
+  /// support it, and also applied to the returned page for those that don't.
+  /// Listing submissions requires an authorization token.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config {
+  ///   authorization_token: Some("token".into()),
+  ///   ..Default::default()
+  /// }).unwrap();
+  ///
+  /// let page = client.list_submissions(1, 20, Some(5)).await.unwrap();
+  /// ```
+  pub async fn list_submissions(
+    &self,
+    page: usize,
+    per_page: usize,
+    status_id: Option<usize>,
+  ) -> Result<SubmissionPage> {
+    let mut endpoint = format!(
+      "/submissions?base64_encoded={}&fields=*&page={page}&per_page={per_page}",
+      self.config.base64_encoded
+    );
 
-    if let Some(authentication_token) = &self.config.authentication_token {
-      headers.insert(
-        header_name(&self.config.authentication_header_name)?,
-        header_value(authentication_token)?,
-      );
+    if let Some(status_id) = status_id {
+      endpoint.push_str(&format!("&status_id={status_id}"));
     }
 
-    if let Some(authorization_token) = &self.config.authorization_token {
-      headers.insert(
-        header_name(&self.config.authorization_header_name)?,
-        header_value(authorization_token)?,
-      );
+    let mut page = self.submission_page(&endpoint).await?;
+
+    if let Some(status_id) = status_id {
+      page.submissions.retain(|submission| {
+        submission
+          .status
+          .as_ref()
+          .is_some_and(|status| status.id == status_id)
+      });
     }
 
-    Ok(headers)
+    Ok(page)
   }
 
-  /// Make an asynchronous request.
-  async fn request<T: DeserializeOwned>(
+  /// List a page of submissions on instances that use cursor-based
+  /// pagination, starting from the first page when `cursor` is `None`.
+  /// Returns the submissions along with the cursor of the next page, if
+  /// there is one.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config {
+  ///   authorization_token: Some("token".into()),
+  ///   ..Default::default()
+  /// }).unwrap();
+  ///
+  /// let mut cursor = None;
+  ///
+  /// loop {
+  ///   let (submissions, next) = client
+  ///     .list_submissions_cursor(cursor.as_deref())
+  ///     .await
+  ///     .unwrap();
+  ///
+  ///   println!("{}", submissions.len());
+  ///
+  ///   match next {
+  ///     Some(next) => cursor = Some(next),
+  ///     None => break,
+  ///   }
+  /// }
+  /// ```
+  pub async fn list_submissions_cursor(
     &self,
-    endpoint: &str,
-    method: Method,
-  ) -> Result<T> {
-    Ok(
-      self
-        .client
-        .request(method, format!("{}{}", self.base_url, endpoint))
-        .headers(self.headers()?)
-        .send()
-        .await?
-        .json::<T>()
-        .await?,
-    )
-  }
+    cursor: Option<&str>,
+  ) -> Result<(Vec<Submission>, Option<String>)> {
+    let page = self.cursor_page(cursor).await?;
 
-  /// Make an asynchronous request with a body.
-  async fn request_with_body<T: DeserializeOwned, B: Serialize>(
-    &self,
-    endpoint: &str,
-    method: Method,
-    body: B,
-  ) -> Result<T> {
-    Ok(
-      self
-        .client
-        .request(method, format!("{}{}", self.base_url, endpoint))
-        .headers(self.headers()?)
-        .body(serde_json::to_string(&body)?)
-        .send()
-        .await?
-        .json::<T>()
-        .await?,
-    )
+    Ok((page.submissions, page.meta.next))
   }
-}
 
-#[cfg(test)]
-mod tests {
-  use {
-    super::*,
-    mockito::{Server, ServerGuard},
-  };
+  /// Fetch a page of submissions by cursor.
+  async fn cursor_page(&self, cursor: Option<&str>) -> Result<SubmissionPage> {
+    let mut endpoint = format!(
+      "/submissions?base64_encoded={}&fields=*&per_page={LIST_PAGE_SIZE}",
+      self.config.base64_encoded
+    );
 
-  struct TestContext {
-    server: ServerGuard,
+    if let Some(cursor) = cursor {
+      endpoint.push_str(&format!("&cursor={}", encode_query_component(cursor)));
+    }
+
+    self.submission_page(&endpoint).await
   }
 
-  impl TestContext {
-    async fn new() -> Self {
-      Self {
-        server: Server::new_async().await,
+  /// Fetch a page of submissions, decoding them if base64 encoded.
+  async fn submission_page(&self, endpoint: &str) -> Result<SubmissionPage> {
+    let mut page = self
+      .request::<SubmissionPage>(endpoint, Method::GET)
+      .await?;
+
+    if self.config.base64_encoded {
+      for submission in &mut page.submissions {
+        submission.decode(self.config.base64_engine())?;
       }
     }
+
+    Ok(page)
   }
 
-  #[tokio::test(flavor = "multi_thread")]
-  async fn languages_ok() {
-    let TestContext { mut server } = TestContext::new().await;
+  /// List every submission by following pagination, fetching at most
+  /// `Config::max_pages` pages. Instances that return a `next` cursor instead
+  /// of a next page number are followed by cursor.
+  ///
+  /// If the cap is reached, the submissions fetched so far are returned.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config {
+  ///   authorization_token: Some("token".into()),
+  ///   ..Default::default()
+  /// }).unwrap();
+  ///
+  /// let submissions = client.list_all_submissions().await.unwrap();
+  /// ```
+  pub async fn list_all_submissions(&self) -> Result<Vec<Submission>> {
+    let mut submissions = Vec::new();
 
-    let client = Client::new(&server.url());
+    let (mut page, mut cursor) = (Some(1), None);
 
-    let body = r#" [
-      { "id": 45, "name": "Assembly (NASM 2.14.02)" },
-      { "id": 46, "name": "Bash (5.0.0)" },
-      { "id": 47, "name": "Basic (FBC 1.07.1)" }
-    ]"#;
+    for _ in 0..self.config.max_pages {
+      let result = match (cursor.as_deref(), page) {
+        (Some(cursor), _) => self.cursor_page(Some(cursor)).await?,
+        (None, Some(current)) => {
+          self.list_submissions(current, LIST_PAGE_SIZE, None).await?
+        }
+        (None, None) => break,
+      };
 
-    let mock = server
-      .mock("GET", "/languages")
-      .with_status(200)
-      .with_header("content-type", "application/json")
-      .with_body(body)
-      .create();
+      submissions.extend(result.submissions);
 
-    let languages = client.get_languages().await.unwrap();
+      page = result.meta.next_page;
+      cursor = result.meta.next;
+    }
 
-    assert_eq!(
-      languages,
-      serde_json::from_str::<Vec<Language>>(body).unwrap()
-    );
+    Ok(submissions)
+  }
 
-    mock.assert();
+  /// List all submissions grouped by language identifier, e.g. for usage
+  /// analytics. Submissions listed without a language are grouped under 0,
+  /// which judge0 never uses as a language identifier. Like
+  /// `list_all_submissions`, at most `Config::max_pages` pages are read.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config {
+  ///   authorization_token: Some("token".into()),
+  ///   ..Default::default()
+  /// }).unwrap();
+  ///
+  /// let submissions = client.submissions_by_language().await.unwrap();
+  /// ```
+  pub async fn submissions_by_language(
+    &self,
+  ) -> Result<HashMap<usize, Vec<Submission>>> {
+    let mut groups = HashMap::<usize, Vec<Submission>>::new();
+
+    for submission in self.list_all_submissions().await? {
+      groups
+        .entry(submission.language_id)
+        .or_default()
+        .push(submission);
+    }
+
+    Ok(groups)
   }
 
-  #[tokio::test(flavor = "multi_thread")]
-  async fn language_ok() {
-    let TestContext { mut server } = TestContext::new().await;
+  /// Delete every submission matching `predicate`, returning how many were
+  /// deleted. All pages are listed before anything is deleted, so deletions
+  /// don't shift the pages still to be read. Like `list_all_submissions`,
+  /// at most `Config::max_pages` pages are considered.
+  ///
+  /// ```rust
+  /// use {
+  ///   chrono::{Duration, Utc},
+  ///   judge0_rs::{Client, Config},
+  /// };
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config {
+  ///   authorization_token: Some("token".into()),
+  ///   ..Default::default()
+  /// }).unwrap();
+  ///
+  /// let cutoff = Utc::now() - Duration::days(30);
+  ///
+  /// let deleted = client
+  ///   .delete_submissions_where(|submission| {
+  ///     submission.created_at.is_some_and(|created_at| created_at < cutoff)
+  ///   })
+  ///   .await
+  ///   .unwrap();
+  /// ```
+  pub async fn delete_submissions_where(
+    &self,
+    predicate: impl Fn(&Submission) -> bool,
+  ) -> Result<usize> {
+    let mut deleted = 0;
 
-    let client = Client::new(&server.url());
+    for submission in self.list_all_submissions().await? {
+      let Some(token) = submission.token.as_deref() else {
+        continue;
+      };
 
-    let body = r#"{
-      "id": 1,
-      "name": "Bash (4.4)",
-      "is_archived": true,
-      "source_file": "script.sh",
-      "compile_cmd": null,
-      "run_cmd": "/usr/local/bash-4.4/bin/bash script.sh"
-    }"#;
+      if predicate(&submission) {
+        self.delete_submission(token, Some("token")).await?;
+        deleted += 1;
+      }
+    }
+
+    Ok(deleted)
+  }
+
+  /// Create a batch submission.
+  ///
+  /// The whole batch is sent base64 encoded if `Config::base64_encoded` is
+  /// set or any submission sets `base64_encoded_override` to true, e.g. for
+  /// binary stdin from `Submission::set_stdin_from_reader`.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config, Submission};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let submissions = vec![
+  ///   Submission {
+  ///     source_code: "print("foo")".into(),
+  ///     language_id: 1,
+  ///     ..Submission::default()
+  ///   },
+  ///   Submission {
+  ///     source_code: "print("bar")".into(),
+  ///     language_id: 1,
+  ///     ..Submission::default()
+  ///   },
+  ///   Submission {
+  ///     source_code: "print("baz")".into(),
+  ///     language_id: 1,
+  ///     ..Submission::default()
+  ///   },
+  /// ];
+  ///
+  /// let result = client.batch_submit(submissions).await.unwrap();
+  /// ```
+  pub async fn batch_submit(
+    &self,
+    submissions: Vec<Submission>,
+  ) -> Result<BatchSubmissionResponse> {
+    let base64_encoded = self.config.base64_encoded
+      || submissions
+        .iter()
+        .any(|submission| submission.base64_encoded_override == Some(true));
+
+    let submissions = submissions
+      .iter()
+      .map(|submission| {
+        submission.request_body(base64_encoded, self.config.base64_engine())
+      })
+      .collect::<Result<Vec<Value>>>()?;
+
+    let mut endpoint =
+      format!("/submissions/batch?base64_encoded={base64_encoded}");
+
+    if self.config.batch_wait {
+      endpoint.push_str("&wait=true");
+    }
+
+    self
+      .request_with_body::<BatchSubmissionResponse, Value>(
+        &endpoint,
+        Method::POST,
+        serde_json::json!({ "submissions": submissions }),
+      )
+      .await
+      .map_err(queue_full)
+  }
+
+  /// Submit submissions in batches of at most `chunk_size`, returning the
+  /// tokens of all created submissions in order.
+  ///
+  /// Fails with `Error::Rejected` as soon as any submission is rejected, in
+  /// which case the earlier batches have already been created.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config, Submission};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let submissions = (0..50)
+  ///   .map(|i| Submission {
+  ///     source_code: format!("print({i})"),
+  ///     language_id: 71,
+  ///     ..Submission::default()
+  ///   })
+  ///   .collect();
+  ///
+  /// let tokens = client.batch_submit_chunked(submissions, 20).await.unwrap();
+  /// ```
+  pub async fn batch_submit_chunked(
+    &self,
+    submissions: Vec<Submission>,
+    chunk_size: usize,
+  ) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+
+    for chunk in submissions.chunks(chunk_size.max(1)) {
+      tokens.extend(
+        self
+          .batch_submit(chunk.to_vec())
+          .await?
+          .tokens()?
+          .into_iter()
+          .map(|token| Token { token }),
+      );
+    }
+
+    Ok(tokens)
+  }
+
+  /// Submit submissions in batches of the instance's maximum batch size,
+  /// `max_submission_batch_size` from `Client::get_config_info`, which is
+  /// fetched once and cached.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config, Submission};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let submissions = (0..50)
+  ///   .map(|i| Submission {
+  ///     source_code: format!("print({i})"),
+  ///     language_id: 71,
+  ///     ..Submission::default()
+  ///   })
+  ///   .collect();
+  ///
+  /// let tokens = client.batch_submit_auto(submissions).await.unwrap();
+  /// ```
+  pub async fn batch_submit_auto(
+    &self,
+    submissions: Vec<Submission>,
+  ) -> Result<Vec<Token>> {
+    let chunk_size = self.cached_config_info().await?.max_submission_batch_size;
+
+    self.batch_submit_chunked(submissions, chunk_size).await
+  }
+
+  /// Get a batch submission.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config, Submission};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let submissions = vec![
+  ///   Submission {
+  ///     source_code: "print("foo")".into(),
+  ///     language_id: 1,
+  ///     ..Submission::default()
+  ///   },
+  ///   Submission {
+  ///     source_code: "print("bar")".into(),
+  ///     language_id: 1,
+  ///     ..Submission::default()
+  ///   },
+  ///   Submission {
+  ///     source_code: "print("baz")".into(),
+  ///     language_id: 1,
+  ///     ..Submission::default()
+  ///   },
+  /// ];
+  ///
+  /// let tokens = client.batch_submit(submissions).await.unwrap().tokens().unwrap();
+  ///
+  /// let batch_submission = client
+  ///   .get_batch_submission(tokens.iter().map(String::as_str).collect(), None)
+  ///   .await
+  ///   .unwrap();
+  /// ```
+  pub async fn get_batch_submission(
+    &self,
+    tokens: Vec<&str>,
+    fields: Option<&[SubmissionField]>,
+  ) -> Result<Vec<Submission>> {
+    let mut submissions = self
+      .request::<Submissions>(
+        &format!(
+          "/submissions/batch?tokens={}&base64_encoded={}&fields={}",
+          tokens.join(","),
+          self.config.base64_encoded,
+          SubmissionField::query(fields)
+        ),
+        Method::GET,
+      )
+      .await?
+      .submissions;
+
+    for submission in &mut submissions {
+      self.fetched(submission, fields)?;
+    }
+
+    Ok(submissions)
+  }
+
+  /// Poll a single submission until it has finished.
+  ///
+  /// Gives up with `Error::Timeout` once `timeout` has elapsed, even in the
+  /// middle of a request. Dropping the returned future cancels the in-flight
+  /// request or sleep immediately, so no further polls are issued.
+  ///
+  /// ```rust
+  /// use {
+  ///   judge0_rs::{Client, Config},
+  ///   std::time::Duration,
+  /// };
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let submission = client
+  ///   .wait_for_submission(
+  ///     "d85cd024-1548-4165-96c7-7bc88673f194",
+  ///     Duration::from_secs(1),
+  ///     Duration::from_secs(30),
+  ///   )
+  ///   .await
+  ///   .unwrap();
+  /// ```
+  pub async fn wait_for_submission(
+    &self,
+    token: &str,
+    interval: Duration,
+    timeout: Duration,
+  ) -> Result<Submission> {
+    time::timeout(timeout, async {
+      loop {
+        let submission = self.get_submission(token, None).await?;
+
+        if submission.is_finished() {
+          return Ok(submission);
+        }
+
+        sleep(self.poll_delay(interval)).await;
+      }
+    })
+    .await
+    .map_err(|_| Error::Timeout)?
+  }
+
+  /// Poll a single submission until it has finished like
+  /// `Client::wait_for_submission`, also returning how long it took.
+  ///
+  /// The client-side `total_wall` time starts when this is called, so call it
+  /// right after creating the submission.
+  ///
+  /// ```rust
+  /// use {
+  ///   judge0_rs::{Client, Config},
+  ///   std::time::Duration,
+  /// };
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let (submission, metrics) = client
+  ///   .wait_for_submission_with_metrics(
+  ///     "d85cd024-1548-4165-96c7-7bc88673f194",
+  ///     Duration::from_secs(1),
+  ///     Duration::from_secs(30),
+  ///   )
+  ///   .await
+  ///   .unwrap();
+  ///
+  /// println!("queued for {:?}", metrics.queue_time);
+  /// ```
+  pub async fn wait_for_submission_with_metrics(
+    &self,
+    token: &str,
+    interval: Duration,
+    timeout: Duration,
+  ) -> Result<(Submission, RunMetrics)> {
+    let start = Instant::now();
+
+    let submission = self.wait_for_submission(token, interval, timeout).await?;
+
+    let metrics = RunMetrics::from_submission(&submission, start.elapsed());
+
+    Ok((submission, metrics))
+  }
+
+  /// Poll a single submission until its status reaches `target`, a status
+  /// id, or a later stage, e.g. to show that it started processing without
+  /// waiting for it to finish. Statuses progress from in queue (1) to
+  /// processing (2) to any finished status, so waiting for processing also
+  /// returns a submission that went straight to a finished status, and
+  /// waiting for any finished status returns once it has finished.
+  ///
+  /// Gives up with `Error::Timeout` once `timeout` has elapsed.
+  ///
+  /// ```rust
+  /// use {
+  ///   judge0_rs::{Client, Config},
+  ///   std::time::Duration,
+  /// };
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let submission = client
+  ///   .wait_until_status(
+  ///     "d85cd024-1548-4165-96c7-7bc88673f194",
+  ///     2,
+  ///     Duration::from_secs(1),
+  ///     Duration::from_secs(30),
+  ///   )
+  ///   .await
+  ///   .unwrap();
+  /// ```
+  pub async fn wait_until_status(
+    &self,
+    token: &str,
+    target: usize,
+    interval: Duration,
+    timeout: Duration,
+  ) -> Result<Submission> {
+    time::timeout(timeout, async {
+      loop {
+        let submission = self.get_submission(token, None).await?;
+
+        let stage = submission
+          .status
+          .as_ref()
+          .map_or(0, |status| status_stage(status.id));
+
+        if stage >= status_stage(target) {
+          return Ok(submission);
+        }
+
+        sleep(self.poll_delay(interval)).await;
+      }
+    })
+    .await
+    .map_err(|_| Error::Timeout)?
+  }
+
+  /// Poll a batch of submissions until all of them have finished, returning
+  /// them in token order.
+  ///
+  /// Gives up with `Error::Timeout` once `timeout` has elapsed.
+  ///
+  /// ```rust
+  /// use {
+  ///   judge0_rs::{Client, Config},
+  ///   std::time::Duration,
+  /// };
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let submissions = client
+  ///   .wait_for_batch(
+  ///     &["d85cd024-1548-4165-96c7-7bc88673f194"],
+  ///     Duration::from_secs(1),
+  ///     Duration::from_secs(30),
+  ///   )
+  ///   .await
+  ///   .unwrap();
+  /// ```
+  pub async fn wait_for_batch(
+    &self,
+    tokens: &[&str],
+    interval: Duration,
+    timeout: Duration,
+  ) -> Result<Vec<Submission>> {
+    time::timeout(timeout, async {
+      loop {
+        let submissions =
+          self.get_batch_submission(tokens.to_vec(), None).await?;
+
+        if submissions.iter().all(Submission::is_finished) {
+          return Ok(submissions);
+        }
+
+        sleep(self.poll_delay(interval)).await;
+      }
+    })
+    .await
+    .map_err(|_| Error::Timeout)?
+  }
+
+  /// Poll several independent batches concurrently until all of them have
+  /// finished, returning the submissions grouped by batch in the same order.
+  /// Each batch is waited on like `wait_for_batch`, so all of them share the
+  /// same `timeout`.
+  ///
+  /// ```rust
+  /// use {
+  ///   judge0_rs::{Client, Config},
+  ///   std::time::Duration,
+  /// };
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let batches = client
+  ///   .wait_for_batches(
+  ///     vec![vec!["a".into(), "b".into()], vec!["c".into()]],
+  ///     Duration::from_secs(1),
+  ///     Duration::from_secs(30),
+  ///   )
+  ///   .await
+  ///   .unwrap();
+  /// ```
+  pub async fn wait_for_batches(
+    &self,
+    batches: Vec<Vec<String>>,
+    interval: Duration,
+    timeout: Duration,
+  ) -> Result<Vec<Vec<Submission>>> {
+    let batches = batches
+      .iter()
+      .map(|tokens| tokens.iter().map(String::as_str).collect::<Vec<_>>())
+      .collect::<Vec<_>>();
+
+    join_all(
+      batches
+        .iter()
+        .map(|tokens| self.wait_for_batch(tokens, interval, timeout)),
+    )
+    .await
+    .into_iter()
+    .collect()
+  }
+
+  /// Resume waiting for a batch from tokens saved before a restart, e.g. the
+  /// strings from `BatchSubmissionResponse::tokens` or serialized `Token`s.
+  /// Behaves like `wait_for_batch`.
+  ///
+  /// ```rust
+  /// use {
+  ///   judge0_rs::{Client, Config},
+  ///   std::time::Duration,
+  /// };
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let tokens: Vec<String> =
+  ///   serde_json::from_str(&std::fs::read_to_string("tokens.json").unwrap())
+  ///     .unwrap();
+  ///
+  /// let submissions = client
+  ///   .resume_batch(tokens, Duration::from_secs(1), Duration::from_secs(30))
+  ///   .await
+  ///   .unwrap();
+  /// ```
+  pub async fn resume_batch(
+    &self,
+    tokens: Vec<String>,
+    interval: Duration,
+    timeout: Duration,
+  ) -> Result<Vec<Submission>> {
+    self
+      .wait_for_batch(
+        &tokens.iter().map(String::as_str).collect::<Vec<_>>(),
+        interval,
+        timeout,
+      )
+      .await
+  }
+
+  /// Poll a batch of submissions once, pairing each token with its
+  /// submission if it has finished, or `None` if it is still queued or
+  /// processing. Useful for handling results as they complete instead of
+  /// waiting for the slowest submission.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// for (token, submission) in client.poll_batch_once(&["a", "b"]).await.unwrap() {
+  ///   if let Some(submission) = submission {
+  ///     println!("{token}: {:?}", submission.stdout);
+  ///   }
+  /// }
+  /// ```
+  pub async fn poll_batch_once(
+    &self,
+    tokens: &[&str],
+  ) -> Result<Vec<(String, Option<Submission>)>> {
+    let submissions = self.get_batch_submission(tokens.to_vec(), None).await?;
+
+    Ok(
+      tokens
+        .iter()
+        .zip(submissions)
+        .map(|(token, submission)| {
+          (
+            (*token).to_owned(),
+            submission.is_finished().then_some(submission),
+          )
+        })
+        .collect(),
+    )
+  }
+
+  /// Run a single source against several test cases, returning the finished
+  /// submissions in the same order as the test cases.
+  ///
+  /// Polling is controlled by `Config::poll_interval` and
+  /// `Config::poll_timeout`.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config, TestCase};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let results = client
+  ///   .grade(
+  ///     "print(input())".into(),
+  ///     71,
+  ///     vec![
+  ///       TestCase {
+  ///         stdin: Some("foo".into()),
+  ///         expected_output: Some("foo".into()),
+  ///       },
+  ///       TestCase {
+  ///         stdin: Some("bar".into()),
+  ///         expected_output: Some("bar".into()),
+  ///       },
+  ///     ],
+  ///   )
+  ///   .await
+  ///   .unwrap();
+  /// ```
+  pub async fn grade(
+    &self,
+    source_code: String,
+    language_id: usize,
+    cases: Vec<TestCase>,
+  ) -> Result<Vec<Submission>> {
+    let submissions = cases
+      .into_iter()
+      .map(|case| Submission {
+        source_code: source_code.clone(),
+        language_id,
+        stdin: case.stdin,
+        expected_output: case.expected_output,
+        ..Default::default()
+      })
+      .collect();
+
+    self.run_batch(submissions).await
+  }
+
+  /// Run a single source against weighted test cases like `grade`, awarding
+  /// each case's points if its output was accepted.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config, WeightedCase};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let score = client
+  ///   .grade_weighted(
+  ///     "print(input())".into(),
+  ///     71,
+  ///     vec![
+  ///       WeightedCase {
+  ///         stdin: Some("foo".into()),
+  ///         expected_output: Some("foo".into()),
+  ///         points: 10,
+  ///       },
+  ///       WeightedCase {
+  ///         stdin: Some("bar".into()),
+  ///         expected_output: Some("bar".into()),
+  ///         points: 30,
+  ///       },
+  ///     ],
+  ///   )
+  ///   .await
+  ///   .unwrap();
+  ///
+  /// println!("{}/{}", score.earned, score.total);
+  /// ```
+  pub async fn grade_weighted(
+    &self,
+    source_code: String,
+    language_id: usize,
+    cases: Vec<WeightedCase>,
+  ) -> Result<ScoreResult> {
+    let points = cases.iter().map(|case| case.points).collect::<Vec<u32>>();
+
+    let submissions = self
+      .grade(
+        source_code,
+        language_id,
+        cases
+          .into_iter()
+          .map(|case| TestCase {
+            stdin: case.stdin,
+            expected_output: case.expected_output,
+          })
+          .collect(),
+      )
+      .await?;
+
+    let per_case = submissions
+      .into_iter()
+      .zip(points.iter())
+      .map(|(submission, &points)| {
+        let accepted = submission
+          .status
+          .as_ref()
+          .is_some_and(|status| status.category() == StatusCategory::Success);
+
+        (submission, if accepted { points } else { 0 })
+      })
+      .collect::<Vec<(Submission, u32)>>();
+
+    Ok(ScoreResult {
+      earned: per_case.iter().map(|(_, earned)| earned).sum(),
+      total: points.iter().sum(),
+      per_case,
+    })
+  }
+
+  /// Submit a batch of submissions and wait for all of them to finish,
+  /// returning the finished submissions in the same order.
+  ///
+  /// Polling is controlled by `Config::poll_interval` and
+  /// `Config::poll_timeout`.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config, Submission};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let results = client
+  ///   .run_batch(vec![
+  ///     Submission {
+  ///       source_code: "print(1)".into(),
+  ///       language_id: 71,
+  ///       ..Submission::default()
+  ///     },
+  ///     Submission {
+  ///       source_code: "print(2)".into(),
+  ///       language_id: 71,
+  ///       ..Submission::default()
+  ///     },
+  ///   ])
+  ///   .await
+  ///   .unwrap();
+  /// ```
+  pub async fn run_batch(
+    &self,
+    submissions: Vec<Submission>,
+  ) -> Result<Vec<Submission>> {
+    let tokens = self.batch_submit(submissions).await?.tokens()?;
+
+    self
+      .wait_for_batch(
+        &tokens.iter().map(String::as_str).collect::<Vec<&str>>(),
+        self.config.poll_interval,
+        self.config.poll_timeout,
+      )
+      .await
+  }
+
+  /// Run a submission to completion and check whether it was accepted,
+  /// returning `Ok(false)` for any other final status, e.g. a wrong answer
+  /// or a compilation error. Errors are reserved for failing to create or
+  /// fetch the submission.
+  ///
+  /// Polling is controlled by `Config::poll_interval` and
+  /// `Config::poll_timeout`.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config, Submission};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let passed = client
+  ///   .run_and_check(Submission {
+  ///     source_code: "print(input())".into(),
+  ///     language_id: 71,
+  ///     stdin: Some("foo".into()),
+  ///     expected_output: Some("foo".into()),
+  ///     ..Submission::default()
+  ///   })
+  ///   .await
+  ///   .unwrap();
+  /// ```
+  pub async fn run_and_check(&self, submission: Submission) -> Result<bool> {
+    let submission = self.run_to_completion(submission).await?;
+
+    Ok(
+      submission
+        .status
+        .is_some_and(|status| status.category() == StatusCategory::Success),
+    )
+  }
+
+  /// Create a submission and wait for it in a background task, sending the
+  /// finished submission, or the error that stopped it, to `tx`. Useful for
+  /// handing results to another part of a service instead of awaiting them.
+  ///
+  /// The task shares the client, so the submission is created through
+  /// `create_submission` with its caches and retries, and polled according
+  /// to `Config::poll_interval` and `Config::poll_timeout`. Must be called
+  /// from within a Tokio runtime.
+  ///
+  /// ```rust
+  /// use {
+  ///   judge0_rs::{Client, Config, Submission},
+  ///   std::sync::Arc,
+  ///   tokio::sync::mpsc,
+  /// };
+  ///
+  /// let client = Arc::new(
+  ///   Client::new("http://localhost:2358").configure(Config::default()).unwrap(),
+  /// );
+  ///
+  /// let (tx, mut rx) = mpsc::channel(16);
+  ///
+  /// client.submit_to_channel(
+  ///   Submission {
+  ///     source_code: "print(1)".into(),
+  ///     language_id: 71,
+  ///     ..Submission::default()
+  ///   },
+  ///   tx,
+  /// );
+  ///
+  /// let submission = rx.recv().await.unwrap().unwrap();
+  /// ```
+  pub fn submit_to_channel(
+    self: &Arc<Self>,
+    submission: Submission,
+    tx: mpsc::Sender<Result<Submission>>,
+  ) -> JoinHandle<()> {
+    let client = Arc::clone(self);
+
+    tokio::spawn(async move {
+      let _ = tx.send(client.run_to_completion(submission).await).await;
+    })
+  }
+
+  /// Create a submission and poll it until it has finished, unless the
+  /// instance already waited for it, according to `Config::poll_interval`
+  /// and `Config::poll_timeout`.
+  async fn run_to_completion(
+    &self,
+    submission: Submission,
+  ) -> Result<Submission> {
+    match self.create_submission(submission).await? {
+      CreateResponse::Completed(submission) if submission.is_finished() => {
+        Ok(*submission)
+      }
+      result => {
+        self
+          .wait_for_submission(
+            result.token(),
+            self.config.poll_interval,
+            self.config.poll_timeout,
+          )
+          .await
+      }
+    }
+  }
+
+  /// Record which fields were requested for a fetched submission and decode
+  /// it if base64 encoding is enabled.
+  fn fetched(
+    &self,
+    submission: &mut Submission,
+    fields: Option<&[SubmissionField]>,
+  ) -> Result {
+    submission.requested_fields = fields.map(|fields| {
+      fields
+        .iter()
+        .map(|field| field.as_str().to_owned())
+        .collect()
+    });
+
+    if self.config.base64_encoded {
+      submission.decode(self.config.base64_engine())?;
+    }
+
+    Ok(())
+  }
+
+  /// How long to sleep between polls, randomized around `interval` when
+  /// `Config::poll_jitter` is set.
+  pub(crate) fn poll_delay(&self, interval: Duration) -> Duration {
+    if self.config.poll_jitter {
+      jitter(interval, &mut rand::rng())
+    } else {
+      interval
+    }
+  }
+
+  /// Build pre-defined headers for each request.
+  fn headers(&self) -> Result<HeaderMap> {
+    if self.config.require_both_auth {
+      if self.config.authentication_token.is_none() {
+        return Err(Error::MissingAuth(
+          self.config.authentication_header_name.clone(),
+        ));
+      }
+
+      if self.config.authorization_token.is_none() {
+        return Err(Error::MissingAuth(
+          self.config.authorization_header_name.clone(),
+        ));
+      }
+    }
+
+    let mut headers = HeaderMap::new();
+
+    let header_name = |name: &str| -> Result<HeaderName> {
+      HeaderName::from_str(name)
+        .map_err(|_| Error::HeaderName(name.to_string()))
+    };
+
+    let header_value = |value: &str| -> Result<HeaderValue> {
+      HeaderValue::from_str(value)
+        .map_err(|_| Error::HeaderName(value.to_string()))
+    };
+
+    headers.insert("content-type", header_value("application/json")?);
+
+    if let Some(accept) = &self.config.accept {
+      headers.insert(ACCEPT, header_value(accept)?);
+    }
+
+    if let Some(authentication_token) = &self.config.authentication_token {
+      headers.insert(
+        header_name(&self.config.authentication_header_name)?,
+        header_value(authentication_token)?,
+      );
+    }
+
+    if let Some(authorization_token) = &self.config.authorization_token {
+      headers.insert(
+        header_name(&self.config.authorization_header_name)?,
+        header_value(authorization_token)?,
+      );
+    }
+
+    if let Some(bearer_token) = &self.config.bearer_token {
+      headers.insert(
+        AUTHORIZATION,
+        header_value(&format!("Bearer {bearer_token}"))?,
+      );
+    }
+
+    Ok(headers)
+  }
+
+  /// Get the judge0 version reported in the `X-Judge0-Version` header of the
+  /// most recent response that included it.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// client.get_about().await.unwrap();
+  ///
+  /// let version = client.server_version();
+  /// ```
+  pub fn server_version(&self) -> Option<String> {
+    self
+      .server_version
+      .lock()
+      .unwrap_or_else(PoisonError::into_inner)
+      .clone()
+  }
+
+  /// Make an asynchronous request with the configured base URL and headers,
+  /// returning the response untouched.
+  ///
+  /// This is an escape hatch for use cases the typed methods don't cover,
+  /// such as custom parsing of the response body.
+  ///
+  /// ```rust
+  /// use {http::Method, judge0_rs::{Client, Config}};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let response = client.raw_request("/about", Method::GET, None).await.unwrap();
+  ///
+  /// let text = response.text().await.unwrap();
+  /// ```
+  pub async fn raw_request(
+    &self,
+    endpoint: &str,
+    method: Method,
+    body: Option<String>,
+  ) -> Result<reqwest::Response> {
+    let mut request = self.prepare(endpoint, method)?;
+
+    if let Some(body) = body {
+      request = request.body(body);
+    }
+
+    self.send(request).await
+  }
+
+  /// Send a prepared request, recording the server version reported in the
+  /// response. Every request to the instance goes through here.
+  async fn send(
+    &self,
+    request: reqwest::RequestBuilder,
+  ) -> Result<reqwest::Response> {
+    let response = request.send().await?;
+
+    if let Some(version) = response
+      .headers()
+      .get(SERVER_VERSION_HEADER)
+      .and_then(|version| version.to_str().ok())
+    {
+      *self
+        .server_version
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner) = Some(version.to_owned());
+    }
+
+    Ok(response)
+  }
+
+  /// The full URL a request to `endpoint` is sent to, e.g. for logging,
+  /// with `Config::path_prefix` in between and exactly one slash between
+  /// each part.
+  ///
+  /// ```rust
+  /// use judge0_rs::Client;
+  ///
+  /// let client = Client::new("http://localhost:2358/");
+  ///
+  /// assert_eq!(
+  ///   client.build_url("/languages"),
+  ///   "http://localhost:2358/languages"
+  /// );
+  /// ```
+  pub fn build_url(&self, endpoint: &str) -> String {
+    let mut url = self.base_url.trim_end_matches('/').to_owned();
+
+    if let Some(prefix) = self
+      .config
+      .path_prefix
+      .as_deref()
+      .map(|prefix| prefix.trim_matches('/'))
+      .filter(|prefix| !prefix.is_empty())
+    {
+      url.push('/');
+      url.push_str(prefix);
+    }
+
+    format!("{url}/{}", endpoint.trim_start_matches('/'))
+  }
+
+  /// Start a request to the given endpoint with the configured headers.
+  pub(crate) fn prepare(
+    &self,
+    endpoint: &str,
+    method: Method,
+  ) -> Result<reqwest::RequestBuilder> {
+    Ok(self.prepare_with_headers(endpoint, method, self.headers()?))
+  }
+
+  /// Start a request to the given endpoint with the configured headers as
+  /// adjusted by the caller, e.g. for a different body encoding.
+  fn prepare_with_headers(
+    &self,
+    endpoint: &str,
+    method: Method,
+    headers: HeaderMap,
+  ) -> reqwest::RequestBuilder {
+    self
+      .client
+      .request(method, self.build_url(endpoint))
+      .headers(headers)
+  }
+
+  /// Check whether the server accepts the configured tokens, treating 401
+  /// and 403 responses as a rejection rather than an error.
+  async fn verify(&self, endpoint: &str) -> Result<bool> {
+    self
+      .with_deadline(async {
+        let response = self.raw_request(endpoint, Method::POST, None).await?;
+
+        match response.status() {
+          StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Ok(false),
+          status if status.is_success() => Ok(true),
+          status => Err(api_error(status, &self.read_body(response).await?)),
+        }
+      })
+      .await
+  }
+
+  /// Make an asynchronous request.
+  async fn request<T: DeserializeOwned>(
+    &self,
+    endpoint: &str,
+    method: Method,
+  ) -> Result<T> {
+    self
+      .with_deadline(async {
+        self
+          .parse(self.raw_request(endpoint, method, None).await?)
+          .await
+      })
+      .await
+  }
+
+  /// Make an asynchronous request with a body, gzipped if
+  /// `Config::compress_request` is set.
+  async fn request_with_body<T: DeserializeOwned, B: Serialize>(
+    &self,
+    endpoint: &str,
+    method: Method,
+    body: B,
+  ) -> Result<T> {
+    let body = serde_json::to_string(&body)?;
+
+    self
+      .with_deadline(self.send_body(endpoint, method, body))
+      .await
+  }
+
+  /// Send a serialized body, gzipped if `Config::compress_request` is set,
+  /// and parse the response.
+  async fn send_body<T: DeserializeOwned>(
+    &self,
+    endpoint: &str,
+    method: Method,
+    body: String,
+  ) -> Result<T> {
+    if !self.config.compress_request {
+      return self
+        .parse(self.raw_request(endpoint, method, Some(body)).await?)
+        .await;
+    }
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+
+    encoder.write_all(body.as_bytes())?;
+
+    let mut headers = self.headers()?;
+
+    headers.insert(CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+
+    self
+      .parse(
+        self
+          .send(
+            self
+              .prepare_with_headers(endpoint, method, headers)
+              .body(encoder.finish()?),
+          )
+          .await?,
+      )
+      .await
+  }
+
+  /// Fail with `Error::Timeout` if `future` doesn't finish within
+  /// `Config::request_deadline`, when set.
+  async fn with_deadline<T>(
+    &self,
+    future: impl Future<Output = Result<T>>,
+  ) -> Result<T> {
+    match self.config.request_deadline {
+      Some(deadline) => time::timeout(deadline, future)
+        .await
+        .map_err(|_| Error::Timeout)?,
+      None => future.await,
+    }
+  }
+
+  /// Make an asynchronous request with a body sent as a multipart form, where
+  /// each non-null field of the body becomes its own part.
+  async fn request_with_form<T: DeserializeOwned, B: Serialize>(
+    &self,
+    endpoint: &str,
+    method: Method,
+    body: B,
+  ) -> Result<T> {
+    let mut form = Form::new();
+
+    if let Value::Object(fields) = serde_json::to_value(&body)? {
+      for (name, value) in fields {
+        match value {
+          Value::Null => {}
+          Value::String(value) => form = form.text(name, value),
+          value => form = form.text(name, value.to_string()),
+        }
+      }
+    }
+
+    let mut headers = self.headers()?;
+
+    headers.remove(CONTENT_TYPE);
+
+    self
+      .with_deadline(async {
+        self
+          .parse(
+            self
+              .send(
+                self
+                  .prepare_with_headers(endpoint, method, headers)
+                  .multipart(form),
+              )
+              .await?,
+          )
+          .await
+      })
+      .await
+  }
+
+  /// Deserialize a successful response body, reporting every unsuccessful
+  /// response as `Error::Api` without looking at its body.
+  async fn parse<T: DeserializeOwned>(
+    &self,
+    response: reqwest::Response,
+  ) -> Result<T> {
+    let status = response.status();
+
+    let endpoint = match response.url().query() {
+      Some(query) => format!("{}?{query}", response.url().path()),
+      None => response.url().path().to_owned(),
+    };
+
+    let body = self.read_body(response).await?;
+
+    if !status.is_success() {
+      return Err(api_error(status, &body));
+    }
+
+    match serde_json::from_slice::<T>(&body) {
+      Ok(value) => Ok(value),
+      Err(source) => Err(Error::Deserialize {
+        endpoint,
+        snippet: truncate(&String::from_utf8_lossy(&body), MAX_SNIPPET_BYTES)
+          .to_owned(),
+        source,
+      }),
+    }
+  }
+
+  /// Read a response body, enforcing `Config::max_response_bytes`.
+  async fn read_body(
+    &self,
+    mut response: reqwest::Response,
+  ) -> Result<Vec<u8>> {
+    let Some(limit) = self.config.max_response_bytes else {
+      return Ok(response.bytes().await?.to_vec());
+    };
+
+    if response
+      .content_length()
+      .is_some_and(|length| length > limit as u64)
+    {
+      return Err(Error::ResponseTooLarge { limit });
+    }
+
+    let mut body = Vec::new();
+
+    while let Some(chunk) = response.chunk().await? {
+      if body.len() + chunk.len() > limit {
+        return Err(Error::ResponseTooLarge { limit });
+      }
+
+      body.extend_from_slice(&chunk);
+    }
+
+    Ok(body)
+  }
+}
+
+pub(crate) fn http_client_builder(
+  config: &Config,
+) -> Result<reqwest::ClientBuilder> {
+  let mut builder =
+    reqwest::Client::builder().redirect(if config.follow_redirects {
+      redirect::Policy::limited(config.max_redirects)
+    } else {
+      redirect::Policy::none()
+    });
+
+  if let Some(connect_timeout) = config.connect_timeout {
+    builder = builder.connect_timeout(connect_timeout);
+  }
+
+  if let Some(pem) = &config.root_cert_pem {
+    builder = builder.add_root_certificate(
+      reqwest::Certificate::from_pem(pem)
+        .map_err(|error| Error::Certificate(error.to_string()))?,
+    );
+  }
+
+  Ok(builder)
+}
+
+/// Truncate `text` to at most `limit` bytes, on a char boundary.
+pub(crate) fn truncate(text: &str, limit: usize) -> &str {
+  let mut end = text.len().min(limit);
+
+  while !text.is_char_boundary(end) {
+    end -= 1;
+  }
+
+  &text[..end]
+}
+
+/// An `Error::Api` for an unsuccessful response, with its body truncated.
+fn api_error(status: StatusCode, body: &[u8]) -> Error {
+  Error::Api {
+    status: status.as_u16(),
+    body: truncate(&String::from_utf8_lossy(body), MAX_ERROR_BODY_BYTES)
+      .to_owned(),
+  }
+}
+
+/// Map judge0's rejection of new submissions while its queue is full, a 503
+/// with a `queue is full` error, to `Error::QueueFull`.
+fn queue_full(error: Error) -> Error {
+  match error {
+    Error::Api { status: 503, body } if body.contains("queue is full") => {
+      Error::QueueFull
+    }
+    error => error,
+  }
+}
+
+/// Map judge0's refusal to wait for a submission on an instance that doesn't
+/// allow waiting to `Error::WaitNotAllowed`.
+fn wait_not_allowed(error: Error) -> Error {
+  match error {
+    Error::Api { body, .. } if body.contains("wait not allowed") => {
+      Error::WaitNotAllowed
+    }
+    error => error,
+  }
+}
+
+/// How far along a submission with the given status id is: in queue,
+/// processing, or finished.
+fn status_stage(id: usize) -> u8 {
+  match id {
+    0 | 1 => 0,
+    2 => 1,
+    _ => 2,
+  }
+}
+
+/// Pick a random duration within half of `interval` in either direction.
+fn jitter(interval: Duration, rng: &mut impl Rng) -> Duration {
+  interval.mul_f64(rng.random_range(0.5..=1.5))
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    mockito::{Server, ServerGuard},
+  };
+
+  struct TestContext {
+    server: ServerGuard,
+  }
+
+  impl TestContext {
+    async fn new() -> Self {
+      Self {
+        server: Server::new_async().await,
+      }
+    }
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn authenticate_ok() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        authentication_token: Some("token".into()),
+        ..Default::default()
+      })
+      .unwrap();
+
+    let mock = server
+      .mock("POST", "/authenticate")
+      .match_header("X-Auth-Token", "token")
+      .with_status(200)
+      .create();
+
+    assert!(client.authenticate().await.unwrap());
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn bearer_token() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        authentication_token: Some("token".into()),
+        bearer_token: Some("jwt".into()),
+        ..Default::default()
+      })
+      .unwrap();
+
+    let mock = server
+      .mock("POST", "/authenticate")
+      .match_header("Authorization", "Bearer jwt")
+      .match_header("X-Auth-Token", "token")
+      .with_status(200)
+      .create();
+
+    assert!(client.authenticate().await.unwrap());
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn authenticate_unauthorized() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        authentication_token: Some("invalid".into()),
+        ..Default::default()
+      })
+      .unwrap();
+
+    let mock = server
+      .mock("POST", "/authenticate")
+      .with_status(401)
+      .create();
+
+    assert!(!client.authenticate().await.unwrap());
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn authorize_forbidden() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        authorization_token: Some("invalid".into()),
+        ..Default::default()
+      })
+      .unwrap();
+
+    let mock = server.mock("POST", "/authorize").with_status(403).create();
+
+    assert!(!client.authorize().await.unwrap());
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn authorize_server_error() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mock = server
+      .mock("POST", "/authorize")
+      .with_status(500)
+      .with_body("internal error")
+      .create();
+
+    assert!(matches!(
+      client.authorize().await,
+      Err(Error::Api { status: 500, body }) if body == "internal error"
+    ));
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn accept_header() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let json = server
+      .mock("GET", "/statuses")
+      .match_header("accept", "application/json")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body("[]")
+      .create();
+
+    Client::new(&server.url()).get_statuses().await.unwrap();
+
+    json.assert();
+
+    let custom = server
+      .mock("GET", "/statuses")
+      .match_header("accept", "application/vnd.judge0+json")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body("[]")
+      .create();
+
+    Client::new(&server.url())
+      .configure(Config {
+        accept: Some("application/vnd.judge0+json".into()),
+        ..Default::default()
+      })
+      .unwrap()
+      .get_statuses()
+      .await
+      .unwrap();
+
+    custom.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn languages_ok() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let body = r#" [
+      { "id": 45, "name": "Assembly (NASM 2.14.02)" },
+      { "id": 46, "name": "Bash (5.0.0)" },
+      { "id": 47, "name": "Basic (FBC 1.07.1)" }
+    ]"#;
+
+    let mock = server
+      .mock("GET", "/languages")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(body)
+      .create();
+
+    let languages = client.get_languages().await.unwrap();
+
+    assert_eq!(
+      languages,
+      serde_json::from_str::<Vec<Language>>(body).unwrap()
+    );
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn language_map() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mock = server
+      .mock("GET", "/languages")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"[
+          { "id": 45, "name": "Assembly (NASM 2.14.02)" },
+          { "id": 71, "name": "Python (3.8.1)" }
+        ]"#,
+      )
+      .expect(1)
+      .create();
+
+    for _ in 0..2 {
+      let map = client.language_map().await.unwrap();
+
+      assert_eq!(map.len(), 2);
+      assert_eq!(map["Assembly (NASM 2.14.02)"], 45);
+      assert_eq!(map["Python (3.8.1)"], 71);
+    }
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn language_ok() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let body = r#"{
+      "id": 1,
+      "name": "Bash (4.4)",
+      "is_archived": true,
+      "source_file": "script.sh",
+      "compile_cmd": null,
+      "run_cmd": "/usr/local/bash-4.4/bin/bash script.sh"
+    }"#;
+
+    let mock = server
+      .mock("GET", "/languages/1")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(body)
+      .create();
+
+    let language = client.get_language(1).await.unwrap();
+
+    assert_eq!(language, serde_json::from_str::<Language>(body).unwrap());
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn statuses_by_category_ok() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let body = r#"[
+      { "id": 1, "description": "In Queue" },
+      { "id": 2, "description": "Processing" },
+      { "id": 3, "description": "Accepted" },
+      { "id": 4, "description": "Wrong Answer" },
+      { "id": 5, "description": "Time Limit Exceeded" }
+    ]"#;
+
+    let mock = server
+      .mock("GET", "/statuses")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(body)
+      .create();
+
+    let statuses = client.get_statuses_by_category().await.unwrap();
+
+    assert_eq!(statuses[&StatusCategory::Queued].len(), 2);
+    assert_eq!(statuses[&StatusCategory::Success].len(), 1);
+    assert_eq!(statuses[&StatusCategory::Failure].len(), 2);
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn about_ok() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let body = r#"{
+      "version": "1.5.0",
+      "homepage": "https://judge0.com",
+      "source_code": "https://github.com/judge0/judge0",
+      "maintainer": "Herman Zvonimir Došilović <hermanz.dosilovic@gmail.com>"
+    }"#;
+
+    let mock = server
+      .mock("GET", "/about")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(body)
+      .create();
+
+    let about = client.get_about().await.unwrap();
+
+    assert_eq!(about, serde_json::from_str::<About>(body).unwrap());
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn check_compatibility() {
+    for (version, compatible) in [("1.13.1", true), ("2.0.0", false)] {
+      let TestContext { mut server } = TestContext::new().await;
+
+      let client = Client::new(&server.url());
+
+      let mock = server
+        .mock("GET", "/about")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+          serde_json::json!({
+            "version": version,
+            "homepage": "https://judge0.com",
+            "source_code": "https://github.com/judge0/judge0",
+            "maintainer": "Herman Zvonimir Došilović"
+          })
+          .to_string(),
+        )
+        .create();
+
+      assert_eq!(
+        client.check_compatibility().await.unwrap(),
+        Compatibility {
+          server_version: version.into(),
+          compatible,
+        }
+      );
+
+      mock.assert();
+    }
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn config_info_ok() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mock = server
+      .mock("GET", "/config_info")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{
+          "maintenance_mode": false,
+          "enable_wait_result": true,
+          "enable_compiler_options": true,
+          "allowed_languages_for_compile_options": [],
+          "max_cpu_time_limit": 15,
+          "max_submission_batch_size": 20,
+          "callbacks_max_tries": 3
+        }"#,
+      )
+      .create();
+
+    let config_info = client.get_config_info().await.unwrap();
+
+    assert!(config_info.enable_compiler_options);
+    assert_eq!(config_info.max_cpu_time_limit, 15.0);
+    assert_eq!(config_info.max_submission_batch_size, 20);
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn capabilities() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mock = server
+      .mock("GET", "/config_info")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{
+          "enable_wait_result": true,
+          "enable_compiler_options": true,
+          "enable_command_line_arguments": true,
+          "enable_submission_delete": false,
+          "enable_callbacks": true,
+          "enable_additional_files": true,
+          "max_cpu_time_limit": 15,
+          "max_wall_time_limit": 20,
+          "max_memory_limit": 512000,
+          "enable_batched_submissions": true,
+          "max_submission_batch_size": 20,
+          "allow_enable_network": false
+        }"#,
+      )
+      .expect(1)
+      .create();
+
+    for _ in 0..2 {
+      assert_eq!(
+        client.capabilities().await.unwrap(),
+        Capabilities {
+          allow_enable_network: false,
+          enable_wait: true,
+          enable_batched_submissions: true,
+          enable_callbacks: true,
+          enable_additional_files: true,
+          enable_compiler_options: true,
+          enable_command_line_arguments: true,
+          enable_submission_delete: false,
+          max_submission_batch_size: 20,
+          max_cpu_time_limit: 15.0,
+          max_wall_time_limit: 20.0,
+          max_memory_limit: 512000.0,
+        }
+      );
+    }
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn ping() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mock = server
+      .mock("GET", "/about")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{
+          "version": "1.5.0",
+          "homepage": "https://judge0.com",
+          "source_code": "https://github.com/judge0/judge0",
+          "maintainer": "Herman Zvonimir Došilović <hermanz.dosilovic@gmail.com>"
+        }"#,
+      )
+      .create();
+
+    assert!(client.ping().await.unwrap() > Duration::ZERO);
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn preflight_missing_language() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        authentication_token: Some("token".into()),
+        ..Default::default()
+      })
+      .unwrap();
+
+    let about = server
+      .mock("GET", "/about")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{
+          "version": "1.5.0",
+          "homepage": "https://judge0.com",
+          "source_code": "https://github.com/judge0/judge0",
+          "maintainer": "Herman Zvonimir Došilović <hermanz.dosilovic@gmail.com>"
+        }"#,
+      )
+      .create();
+
+    let authenticate = server
+      .mock("POST", "/authenticate")
+      .match_header("x-auth-token", "token")
+      .with_status(200)
+      .create();
+
+    let language = server
+      .mock("GET", "/languages/999")
+      .with_status(404)
+      .with_body(r#"{ "error": "language not found" }"#)
+      .create();
+
+    match client.preflight(999).await {
+      Err(Error::Preflight(failures)) => {
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].starts_with("language 999 is unavailable"));
+      }
+      result => panic!("unexpected result: {result:?}"),
+    }
+
+    about.assert();
+    authenticate.assert();
+    language.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn workers_ok() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let body = r#"[{
+      "queue": "default",
+      "size": 0,
+      "available": 1,
+      "idle": 1,
+      "working": 0,
+      "paused": 0,
+      "failed": 0
+    }]"#;
+
+    let mock = server
+      .mock("GET", "/workers")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(body)
+      .create();
+
+    let workers = client.get_workers().await.unwrap();
+
+    assert_eq!(workers, serde_json::from_str::<Vec<Worker>>(body).unwrap());
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn create_submission_ok() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let body = r#"{
+      "token": "d85cd024-1548-4165-96c7-7bc88673f194"
+    }"#;
+
+    let mock = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(body)
+      .create();
+
+    let result = client
+      .create_submission(Submission {
+        source_code: r#"print("Hello, world!")"#.into(),
+        language_id: 1,
+        ..Default::default()
+      })
+      .await
+      .unwrap();
+
+    assert!(matches!(
+      result,
+      CreateResponse::Pending(Token { token })
+        if token == "d85cd024-1548-4165-96c7-7bc88673f194"
+    ));
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn create_submission_accepted() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mock = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .with_status(202)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "token": "a" }"#)
+      .create();
+
+    let result = client
+      .create_submission(Submission {
+        source_code: r#"print("Hello, world!")"#.into(),
+        language_id: 1,
+        ..Default::default()
+      })
+      .await
+      .unwrap();
+
+    assert_eq!(result.token(), "a");
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn create_submission_raw() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let body = r#"{"extra":[1,2],"language_id":"71","source_code":"print(1)"}"#;
+
+    let mock = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .match_header("content-type", "application/json")
+      .match_body(body)
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "token": "a" }"#)
+      .create();
+
+    let result = client
+      .create_submission_raw(serde_json::from_str(body).unwrap())
+      .await
+      .unwrap();
+
+    assert_eq!(result["token"], "a");
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn get_submission_not_found() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mock = server
+      .mock("GET", mockito::Matcher::Regex("^/submissions/a".into()))
+      .with_status(404)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "error": "Not Found" }"#)
+      .create();
+
+    let error = client.get_submission("a", None).await.unwrap_err();
+
+    assert!(matches!(error, Error::Api { status: 404, .. }));
+    assert_eq!(error.status_code(), Some(404));
+    assert!(!error.is_rate_limited());
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn delete_submission_forbidden() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mock = server
+      .mock("DELETE", "/submissions/a?fields=*")
+      .with_status(403)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "error": "delete not allowed" }"#)
+      .create();
+
+    let error = client.delete_submission("a", None).await.unwrap_err();
+
+    assert_eq!(error.status_code(), Some(403));
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn create_submission_rate_limited() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mock = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .with_status(429)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "error": "too many requests" }"#)
+      .create();
+
+    let error = client
+      .create_submission(Submission {
+        source_code: "print(1)".into(),
+        language_id: 71,
+        ..Default::default()
+      })
+      .await
+      .unwrap_err();
+
+    assert_eq!(error.status_code(), Some(429));
+    assert!(error.is_rate_limited());
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn create_submission_raw_queue_full() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mock = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .with_status(503)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "error": "queue is full" }"#)
+      .create();
+
+    assert!(matches!(
+      client
+        .create_submission_raw(serde_json::json!({ "source_code": "print(1)" }))
+        .await,
+      Err(Error::QueueFull)
+    ));
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn create_submission_queue_full() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let submission = Submission {
+      source_code: "print(1)".into(),
+      language_id: 71,
+      ..Default::default()
+    };
+
+    let endpoint = "/submissions?base64_encoded=false&wait=false";
+
+    let full = server
+      .mock("POST", endpoint)
+      .with_status(503)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "error": "queue is full" }"#)
+      .expect(2)
+      .create();
+
+    assert!(matches!(
+      Client::new(&server.url())
+        .create_submission(submission.clone())
+        .await,
+      Err(Error::QueueFull)
+    ));
+
+    let created = server
+      .mock("POST", endpoint)
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "token": "a" }"#)
+      .create();
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        poll_interval: Duration::ZERO,
+        queue_full_retries: 3,
+        ..Default::default()
+      })
+      .unwrap();
+
+    let result = client.create_submission(submission).await.unwrap();
+
+    assert_eq!(result.token(), "a");
+
+    full.assert();
+    created.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn create_submission_invalid_language() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let body = r#"{
+      "language_id": ["language with id 9000 doesn't exist"]
+    }"#;
+
+    let mock = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .with_status(422)
+      .with_header("content-type", "application/json")
+      .with_body(body)
+      .create();
+
+    let result = client
+      .create_submission(Submission {
+        source_code: r#"print("Hello, world!")"#.into(),
+        language_id: 9000,
+        ..Default::default()
+      })
+      .await;
+
+    let error = result.unwrap_err();
+
+    assert!(error.is_validation());
+    assert_eq!(error.status_code(), Some(422));
+    assert!(matches!(
+      error,
+      Error::Api { body: error, .. }
+        if serde_json::from_str::<Value>(&error).unwrap()
+          == serde_json::from_str::<Value>(body).unwrap()
+    ));
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn create_submission_invalid_wall_time_limit() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let body = r#"{
+      "wall_time_limit": ["must be less than or equal to 150"]
+    }"#;
+
+    let mock = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .with_status(422)
+      .with_header("content-type", "application/json")
+      .with_body(body)
+      .create();
+
+    let result = client
+      .create_submission(Submission {
+        source_code: r#"print("Hello, world!")"#.into(),
+        language_id: 4,
+        number_of_runs: Some(1),
+        stdin: Some("Judge0".into()),
+        expected_output: Some("hello, Judge0".into()),
+        cpu_time_limit: Some(1.0),
+        cpu_extra_time: Some(0.5),
+        wall_time_limit: Some(100000.0),
+        memory_limit: Some(128000.0),
+        stack_limit: Some(128000),
+        enable_per_process_and_thread_time_limit: Some(false),
+        enable_per_process_and_thread_memory_limit: Some(false),
+        max_file_size: Some(1024),
+        ..Default::default()
+      })
+      .await;
+
+    let error = result.unwrap_err();
+
+    assert!(error.is_validation());
+    assert_eq!(error.status_code(), Some(422));
+    assert!(matches!(
+      error,
+      Error::Api { body: error, .. }
+        if serde_json::from_str::<Value>(&error).unwrap()
+          == serde_json::from_str::<Value>(body).unwrap()
+    ));
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn create_submission_invalid_utf8() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        wait: true,
+        ..Default::default()
+      })
+      .unwrap();
+
+    let body = r#"{
+      "token": "fcd0de6d-ee52-4a9d-8a00-6e0d98d394cf",
+      "error": "some attributes for this submission cannot be converted to UTF-8, use base64_encoded=true query parameter"
+    }"#;
+
+    let mock = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=true")
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(body)
+      .create();
+
+    let result = client
+      .create_submission(Submission {
+        source_code: r#""print(\"\xFE\")""#.into(),
+        language_id: 70,
+        ..Default::default()
+      })
+      .await;
+
+    assert!(matches!(
+      result,
+      Err(Error::Rejected(value))
+        if value == serde_json::from_str::<Value>(body).unwrap()
+    ));
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn create_submission_wait_for_finish() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        wait: true,
+        ..Default::default()
+      })
+      .unwrap();
+
+    let body = r#"{
+      "stdout": "hello, Judge0\n",
+      "time": "0.001",
+      "memory": 380,
+      "stderr": null,
+      "token": "eb0dd001-66db-47f4-8a69-b736c9bc23f6",
+      "compile_output": null,
+      "message": null,
+      "status": {
+        "id": 3,
+        "description": "Accepted"
+      }
+    }"#;
+
+    let mock = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=true")
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(body)
+      .create();
+
+    let result = client
+      .create_submission(Submission {
+        source_code: r#"
+          #include <stdio.h>
+
+          int main(void) {
+            char name[10];
+            scanf("%s", name);
+            printf("hello, %s\n", name);
+            return 0;
+          }"#
+          .into(),
+        language_id: 4,
+        stdin: Some("Judge0".into()),
+        expected_output: Some("hello, Judge0".into()),
+        ..Default::default()
+      })
+      .await
+      .unwrap();
+
+    let CreateResponse::Completed(submission) = result else {
+      panic!("expected a completed submission, got {result:?}");
+    };
+
+    assert_eq!(
+      submission.token.as_deref(),
+      Some("eb0dd001-66db-47f4-8a69-b736c9bc23f6")
+    );
+    assert_eq!(submission.stdout.as_deref(), Some("hello, Judge0\n"));
+    assert_eq!(submission.status.unwrap().id, 3);
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn create_submission_wait_override() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let body = r#"{
+      "token": "d85cd024-1548-4165-96c7-7bc88673f194"
+    }"#;
+
+    let mock = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=true")
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(body)
+      .create();
+
+    let submission = Submission {
+      source_code: r#"print("Hello, world!")"#.into(),
+      language_id: 1,
+      wait_override: Some(true),
+      ..Default::default()
+    };
+
+    assert!(!serde_json::to_string(&submission)
+      .unwrap()
+      .contains("wait_override"));
+
+    let result = client.create_submission(submission).await.unwrap();
+
+    assert!(matches!(
+      result,
+      CreateResponse::Pending(Token { token })
+        if token == "d85cd024-1548-4165-96c7-7bc88673f194"
+    ));
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn create_submission_multipart() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        use_multipart: true,
+        ..Default::default()
+      })
+      .unwrap();
+
+    let body = r#"{
+      "token": "d85cd024-1548-4165-96c7-7bc88673f194"
+    }"#;
+
+    let mock = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .match_header(
+        "content-type",
+        mockito::Matcher::Regex("^multipart/form-data; boundary=".into()),
+      )
+      .match_body(mockito::Matcher::AllOf(vec![
+        mockito::Matcher::Regex(r#"name="source_code"\r\n\r\nputs 1"#.into()),
+        mockito::Matcher::Regex(r#"name="language_id"\r\n\r\n72"#.into()),
+      ]))
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(body)
+      .create();
+
+    let result = client
+      .create_submission(Submission {
+        source_code: "puts 1".into(),
+        language_id: 72,
+        ..Default::default()
+      })
+      .await
+      .unwrap();
+
+    assert!(matches!(
+      result,
+      CreateResponse::Pending(Token { token })
+        if token == "d85cd024-1548-4165-96c7-7bc88673f194"
+    ));
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn raw_request_ok() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        authentication_token: Some("token".into()),
+        ..Default::default()
+      })
+      .unwrap();
+
+    let mock = server
+      .mock("POST", "/custom")
+      .match_header("content-type", "application/json")
+      .match_header("X-Auth-Token", "token")
+      .match_body("not json")
+      .with_status(200)
+      .with_body("not json either")
+      .create();
+
+    let response = client
+      .raw_request("/custom", Method::POST, Some("not json".into()))
+      .await
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.text().await.unwrap(), "not json either");
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn create_submission_wait_not_allowed() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        wait: true,
+        ..Default::default()
+      })
+      .unwrap();
+
+    let mock = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=true")
+      .with_status(400)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "error": "wait not allowed" }"#)
+      .create();
+
+    let result = client
+      .create_submission(Submission {
+        source_code: r#"print("Hello, world!")"#.into(),
+        language_id: 71,
+        ..Default::default()
+      })
+      .await;
+
+    assert!(matches!(result, Err(Error::WaitNotAllowed)));
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn create_submission_compiler_options() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        compiler_options: HashMap::from([(54, "-std=c++20".into())]),
+        ..Default::default()
+      })
+      .unwrap();
+
+    let configured = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .match_body(mockito::Matcher::PartialJsonString(
+        r#"{ "language_id": 54, "compiler_options": "-std=c++20" }"#.into(),
+      ))
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "token": "a" }"#)
+      .create();
+
+    let explicit = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .match_body(mockito::Matcher::PartialJsonString(
+        r#"{ "language_id": 54, "compiler_options": "-std=c++17" }"#.into(),
+      ))
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "token": "b" }"#)
+      .create();
+
+    client
+      .create_submission(Submission {
+        source_code: "int main() {}".into(),
+        language_id: 54,
+        ..Default::default()
+      })
+      .await
+      .unwrap();
+
+    client
+      .create_submission(Submission {
+        source_code: "int main() {}".into(),
+        language_id: 54,
+        compiler_options: Some("-std=c++17".into()),
+        ..Default::default()
+      })
+      .await
+      .unwrap();
+
+    configured.assert();
+    explicit.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn create_submission_input_too_large() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        max_input_bytes: Some(16),
+        ..Default::default()
+      })
+      .unwrap();
+
+    let mock = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "token": "a" }"#)
+      .expect(1)
+      .create();
+
+    let result = client
+      .create_submission(Submission {
+        source_code: "print(input())".into(),
+        stdin: Some("a".repeat(100)),
+        language_id: 71,
+        ..Default::default()
+      })
+      .await;
+
+    assert!(matches!(
+      result,
+      Err(Error::InputTooLarge {
+        size: 114,
+        limit: 16
+      })
+    ));
+
+    client
+      .create_submission(Submission {
+        source_code: "print(input())".into(),
+        stdin: Some("a".into()),
+        language_id: 71,
+        ..Default::default()
+      })
+      .await
+      .unwrap();
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn create_submission_default_language() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        default_language_id: Some(71),
+        ..Default::default()
+      })
+      .unwrap();
+
+    let default = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .match_body(mockito::Matcher::PartialJsonString(
+        r#"{ "source_code": "print(1)", "language_id": 71 }"#.into(),
+      ))
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "token": "a" }"#)
+      .create();
+
+    let explicit = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .match_body(mockito::Matcher::PartialJsonString(
+        r#"{ "source_code": "puts 1", "language_id": 72 }"#.into(),
+      ))
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "token": "b" }"#)
+      .create();
+
+    client
+      .create_submission(Submission {
+        source_code: "print(1)".into(),
+        ..Default::default()
+      })
+      .await
+      .unwrap();
+
+    client
+      .create_submission(Submission {
+        source_code: "puts 1".into(),
+        language_id: 72,
+        ..Default::default()
+      })
+      .await
+      .unwrap();
+
+    default.assert();
+    explicit.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn create_submission_dedup() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        dedup_cache_size: 1,
+        ..Default::default()
+      })
+      .unwrap();
+
+    let mock = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "token": "a" }"#)
+      .expect(2)
+      .create();
+
+    let submission = |stdin: &str| Submission {
+      source_code: "print(input())".into(),
+      language_id: 71,
+      stdin: Some(stdin.into()),
+      ..Default::default()
+    };
+
+    for _ in 0..2 {
+      let result = client.create_submission(submission("foo")).await.unwrap();
+
+      assert_eq!(result.token(), "a");
+    }
+
+    client.create_submission(submission("bar")).await.unwrap();
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn create_submission_dedup_all_fields() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        dedup_cache_size: 8,
+        ..Default::default()
+      })
+      .unwrap();
+
+    let create = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "token": "a" }"#)
+      .expect(3)
+      .create();
+
+    let create_wait = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=true")
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "token": "b" }"#)
+      .expect(1)
+      .create();
+
+    let submission = Submission {
+      source_code: "print(input())".into(),
+      language_id: 71,
+      stdin: Some("foo".into()),
+      ..Default::default()
+    };
+
+    for submission in [
+      submission.clone(),
+      Submission {
+        expected_output: Some("foo".into()),
+        ..submission.clone()
+      },
+      Submission {
+        cpu_time_limit: Some(1.0),
+        ..submission.clone()
+      },
+      Submission {
+        wait_override: Some(true),
+        ..submission.clone()
+      },
+      submission,
+    ] {
+      client.create_submission(submission).await.unwrap();
+    }
+
+    create.assert();
+    create_wait.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn create_submission_dedup_after_defaults() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        dedup_cache_size: 8,
+        default_language_id: Some(71),
+        compiler_options: HashMap::from([(71, "-O".into())]),
+        ..Default::default()
+      })
+      .unwrap();
+
+    let create = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .match_body(mockito::Matcher::PartialJsonString(
+        r#"{ "language_id": 71, "compiler_options": "-O" }"#.into(),
+      ))
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "token": "a" }"#)
+      .expect(1)
+      .create();
+
+    let submission = Submission {
+      source_code: "print(1)".into(),
+      ..Default::default()
+    };
+
+    for submission in [
+      submission.clone(),
+      Submission {
+        language_id: 71,
+        compiler_options: Some("-O".into()),
+        ..submission
+      },
+    ] {
+      assert_eq!(
+        client.create_submission(submission).await.unwrap().token(),
+        "a"
+      );
+    }
+
+    create.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn create_submission_compressed() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        compress_request: true,
+        ..Default::default()
+      })
+      .unwrap();
+
+    let mock = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .match_header("content-encoding", "gzip")
+      .match_request(|request| {
+        let mut body = String::new();
+
+        flate2::read::GzDecoder::new(&request.body().unwrap()[..])
+          .read_to_string(&mut body)
+          .unwrap();
+
+        serde_json::from_str::<Value>(&body).unwrap()
+          == serde_json::json!({
+            "source_code": r#"print("Hello, world!")"#,
+            "language_id": 71
+          })
+      })
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "token": "a" }"#)
+      .create();
+
+    let result = client
+      .create_submission(Submission {
+        source_code: r#"print("Hello, world!")"#.into(),
+        language_id: 71,
+        ..Default::default()
+      })
+      .await
+      .unwrap();
+
+    assert_eq!(result.token(), "a");
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn create_submission_wait_base64_encoded() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        base64_encoded: true,
+        wait: true,
+        ..Default::default()
+      })
+      .unwrap();
+
+    let mock = server
+      .mock("POST", "/submissions?base64_encoded=true&wait=true")
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{
+          "token": "a",
+          "stdout": "SGVsbG8sIHdvcmxkIQo=",
+          "status": { "id": 3, "description": "Accepted" }
+        }"#,
+      )
+      .create();
+
+    let result = client
+      .create_submission(Submission {
+        source_code: r#"print("Hello, world!")"#.into(),
+        language_id: 71,
+        ..Default::default()
+      })
+      .await
+      .unwrap();
+
+    assert_eq!(result.token(), "a");
+
+    let CreateResponse::Completed(submission) = result else {
+      panic!("expected a completed submission, got {result:?}");
+    };
+
+    assert_eq!(submission.stdout.as_deref(), Some("Hello, world!\n"));
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn create_submission_base64_encoded() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let config = Config {
+      base64_encoded: true,
+      ..Default::default()
+    };
+
+    let submission = Submission {
+      source_code: r#"print("Hello, world!")"#.into(),
+      language_id: 71,
+      expected_output: Some("Hello, world!".into()),
+      ..Default::default()
+    };
+
+    let mock = server
+      .mock("POST", "/submissions?base64_encoded=true&wait=false")
+      .match_body(mockito::Matcher::AllOf(vec![
+        mockito::Matcher::JsonString(
+          submission.to_request_json(&config).unwrap(),
+        ),
+        mockito::Matcher::PartialJsonString(
+          r#"{ "source_code": "cHJpbnQoIkhlbGxvLCB3b3JsZCEiKQ==" }"#.into(),
+        ),
+      ]))
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "token": "d85cd024-1548-4165-96c7-7bc88673f194" }"#)
+      .create();
+
+    Client::new(&server.url())
+      .configure(config)
+      .unwrap()
+      .create_submission(submission)
+      .await
+      .unwrap();
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn server_version_captured() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mock = server
+      .mock("GET", "/about")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_header("X-Judge0-Version", "1.13.0")
+      .with_body(
+        r#"{
+          "version": "1.13.0",
+          "homepage": "https://judge0.com",
+          "source_code": "https://github.com/judge0/judge0",
+          "maintainer": "Herman Zvonimir Došilović <hermanz.dosilovic@gmail.com>"
+        }"#,
+      )
+      .create();
+
+    assert_eq!(client.server_version(), None);
+
+    client.get_about().await.unwrap();
+
+    assert_eq!(client.server_version(), Some("1.13.0".into()));
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn server_version_captured_compressed_and_multipart() {
+    for (config, version) in [
+      (
+        Config {
+          compress_request: true,
+          ..Default::default()
+        },
+        "1.13.0",
+      ),
+      (
+        Config {
+          use_multipart: true,
+          ..Default::default()
+        },
+        "1.13.1",
+      ),
+    ] {
+      let TestContext { mut server } = TestContext::new().await;
+
+      let client = Client::new(&server.url()).configure(config).unwrap();
+
+      let mock = server
+        .mock("POST", "/submissions?base64_encoded=false&wait=false")
+        .match_header("accept", "application/json")
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_header("X-Judge0-Version", version)
+        .with_body(r#"{ "token": "a" }"#)
+        .create();
+
+      client
+        .create_submission(Submission {
+          source_code: "print(1)".into(),
+          language_id: 71,
+          ..Default::default()
+        })
+        .await
+        .unwrap();
+
+      assert_eq!(client.server_version(), Some(version.into()));
+
+      mock.assert();
+    }
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn grade_weighted() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        poll_interval: Duration::ZERO,
+        ..Default::default()
+      })
+      .unwrap();
+
+    let create = server
+      .mock("POST", "/submissions/batch?base64_encoded=false")
+      .match_body(mockito::Matcher::PartialJsonString(
+        r#"{
+          "submissions": [
+            { "source_code": "print(input())", "language_id": 71, "stdin": "foo", "expected_output": "foo" },
+            { "source_code": "print(input())", "language_id": 71, "stdin": "bar", "expected_output": "baz" }
+          ]
+        }"#
+        .into(),
+      ))
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(r#"[{ "token": "a" }, { "token": "b" }]"#)
+      .create();
+
+    let finished = server
+      .mock("GET", "/submissions/batch?tokens=a,b&base64_encoded=false&fields=*")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{
+          "submissions": [
+            { "token": "a", "status": { "id": 3, "description": "Accepted" } },
+            { "token": "b", "status": { "id": 4, "description": "Wrong Answer" } }
+          ]
+        }"#,
+      )
+      .create();
+
+    let score = client
+      .grade_weighted(
+        "print(input())".into(),
+        71,
+        vec![
+          WeightedCase {
+            stdin: Some("foo".into()),
+            expected_output: Some("foo".into()),
+            points: 10,
+          },
+          WeightedCase {
+            stdin: Some("bar".into()),
+            expected_output: Some("baz".into()),
+            points: 30,
+          },
+        ],
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(score.earned, 10);
+    assert_eq!(score.total, 40);
+    assert_eq!(
+      score
+        .per_case
+        .iter()
+        .map(|(_, earned)| *earned)
+        .collect::<Vec<u32>>(),
+      vec![10, 0]
+    );
+
+    create.assert();
+    finished.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn grade_ok() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        poll_interval: Duration::ZERO,
+        ..Default::default()
+      })
+      .unwrap();
+
+    let create = server
+      .mock("POST", "/submissions/batch?base64_encoded=false")
+      .match_body(mockito::Matcher::PartialJsonString(
+        r#"{
+          "submissions": [
+            { "source_code": "print(input())", "language_id": 71, "stdin": "foo", "expected_output": "foo" },
+            { "source_code": "print(input())", "language_id": 71, "stdin": "bar", "expected_output": "baz" }
+          ]
+        }"#
+        .into(),
+      ))
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(r#"[{ "token": "a" }, { "token": "b" }]"#)
+      .create();
+
+    let processing = server
+      .mock(
+        "GET",
+        "/submissions/batch?tokens=a,b&base64_encoded=false&fields=*",
+      )
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{
+          "submissions": [
+            { "token": "a", "status": { "id": 3, "description": "Accepted" } },
+            { "token": "b", "status": { "id": 2, "description": "Processing" } }
+          ]
+        }"#,
+      )
+      .expect(1)
+      .create();
+
+    let finished = server
+      .mock("GET", "/submissions/batch?tokens=a,b&base64_encoded=false&fields=*")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{
+          "submissions": [
+            { "token": "a", "status": { "id": 3, "description": "Accepted" } },
+            { "token": "b", "status": { "id": 4, "description": "Wrong Answer" } }
+          ]
+        }"#,
+      )
+      .create();
+
+    let results = client
+      .grade(
+        "print(input())".into(),
+        71,
+        vec![
+          TestCase {
+            stdin: Some("foo".into()),
+            expected_output: Some("foo".into()),
+          },
+          TestCase {
+            stdin: Some("bar".into()),
+            expected_output: Some("baz".into()),
+          },
+        ],
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(
+      results
+        .iter()
+        .map(|submission| submission.status.as_ref().unwrap().id)
+        .collect::<Vec<usize>>(),
+      vec![3, 4]
+    );
+
+    create.assert();
+    processing.assert();
+    finished.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn get_batch_submission_fields() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mock = server
+      .mock(
+        "GET",
+        "/submissions/batch?tokens=a,b&base64_encoded=false&fields=stdout,status",
+      )
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{
+          "submissions": [
+            { "stdout": "1\n", "status": { "id": 3, "description": "Accepted" } },
+            { "stdout": "2\n", "status": { "id": 3, "description": "Accepted" } }
+          ]
+        }"#,
+      )
+      .create();
+
+    let submissions = client
+      .get_batch_submission(
+        vec!["a", "b"],
+        Some(&[SubmissionField::Stdout, SubmissionField::Status]),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(submissions.len(), 2);
+
+    for submission in submissions {
+      assert!(submission.was_fetched("stdout"));
+      assert!(!submission.was_fetched("stderr"));
+    }
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn poll_batch_once_partial() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mock = server
+      .mock(
+        "GET",
+        "/submissions/batch?tokens=a,b,c&base64_encoded=false&fields=*",
+      )
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{
+          "submissions": [
+            { "token": "a", "status": { "id": 3, "description": "Accepted" } },
+            { "token": "b", "status": { "id": 2, "description": "Processing" } },
+            { "token": "c", "status": { "id": 4, "description": "Wrong Answer" } }
+          ]
+        }"#,
+      )
+      .create();
+
+    let results = client.poll_batch_once(&["a", "b", "c"]).await.unwrap();
+
+    assert_eq!(
+      results
+        .iter()
+        .map(|(token, submission)| (
+          token.as_str(),
+          submission.as_ref().map(|submission| submission
+            .status
+            .as_ref()
+            .unwrap()
+            .id)
+        ))
+        .collect::<Vec<_>>(),
+      vec![("a", Some(3)), ("b", None), ("c", Some(4))]
+    );
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn batch_submit_mixed() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mock = server
+      .mock("POST", "/submissions/batch?base64_encoded=false")
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"[
+          { "token": "a" },
+          { "language_id": ["language with id 9000 doesn't exist"] },
+          { "token": "b" }
+        ]"#,
+      )
+      .create();
+
+    let response = client
+      .batch_submit(vec![Submission::default(); 3])
+      .await
+      .unwrap();
+
+    assert_eq!(
+      response.0,
+      vec![
+        BatchToken::Token(Token { token: "a".into() }),
+        BatchToken::Error(HashMap::from([(
+          "language_id".into(),
+          serde_json::json!(["language with id 9000 doesn't exist"])
+        )])),
+        BatchToken::Token(Token { token: "b".into() }),
+      ]
+    );
+
+    assert!(matches!(response.tokens(), Err(Error::Rejected(_))));
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn batch_submit_binary_stdin() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mut binary = Submission {
+      source_code: "print(1)".into(),
+      language_id: 71,
+      ..Default::default()
+    };
+
+    binary
+      .set_stdin_from_reader(&[0, 159, 146, 150][..])
+      .unwrap();
+
+    let mock = server
+      .mock("POST", "/submissions/batch?base64_encoded=true")
+      .match_body(mockito::Matcher::PartialJsonString(
+        r#"{
+          "submissions": [
+            { "source_code": "cHJpbnQoMSk=", "stdin": "AJ+Slg==" },
+            { "source_code": "cHJpbnQoMik=" }
+          ]
+        }"#
+          .into(),
+      ))
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(r#"[{ "token": "a" }, { "token": "b" }]"#)
+      .create();
+
+    let response = client
+      .batch_submit(vec![
+        binary,
+        Submission {
+          source_code: "print(2)".into(),
+          language_id: 71,
+          ..Default::default()
+        },
+      ])
+      .await
+      .unwrap();
+
+    assert_eq!(response.tokens().unwrap(), vec!["a", "b"]);
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn batch_submit_wait() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        batch_wait: true,
+        ..Default::default()
+      })
+      .unwrap();
+
+    let mock = server
+      .mock("POST", "/submissions/batch?base64_encoded=false&wait=true")
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(r#"[{ "token": "a" }]"#)
+      .create();
+
+    let response = client
+      .batch_submit(vec![Submission::default()])
+      .await
+      .unwrap();
+
+    assert_eq!(response.tokens().unwrap(), vec!["a"]);
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn batch_submit_auto() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let config_info = server
+      .mock("GET", "/config_info")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "max_submission_batch_size": 2 }"#)
+      .expect(1)
+      .create();
+
+    let mut chunks = Vec::new();
+
+    for (first, body) in [
+      (0, r#"[{ "token": "a" }, { "token": "b" }]"#),
+      (2, r#"[{ "token": "c" }, { "token": "d" }]"#),
+      (4, r#"[{ "token": "e" }]"#),
+    ] {
+      chunks.push(
+        server
+          .mock("POST", "/submissions/batch?base64_encoded=false")
+          .match_body(mockito::Matcher::Regex(format!(
+            r#"^\{{"submissions":\[\{{[^}}]*"source_code":"print\({first}\)""#
+          )))
+          .with_status(201)
+          .with_header("content-type", "application/json")
+          .with_body(body)
+          .expect(1)
+          .create(),
+      );
+    }
+
+    let submissions = (0..5)
+      .map(|i| Submission {
+        source_code: format!("print({i})"),
+        language_id: 71,
+        ..Default::default()
+      })
+      .collect();
+
+    let tokens = client.batch_submit_auto(submissions).await.unwrap();
+
+    assert_eq!(
+      tokens
+        .iter()
+        .map(|token| token.token.as_str())
+        .collect::<Vec<&str>>(),
+      vec!["a", "b", "c", "d", "e"]
+    );
+
+    config_info.assert();
+
+    for chunk in chunks {
+      chunk.assert();
+    }
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn run_and_check() {
+    for (status, passed) in [
+      (r#"{ "id": 3, "description": "Accepted" }"#, true),
+      (r#"{ "id": 4, "description": "Wrong Answer" }"#, false),
+    ] {
+      let TestContext { mut server } = TestContext::new().await;
+
+      let client = Client::new(&server.url())
+        .configure(Config {
+          poll_interval: Duration::ZERO,
+          ..Default::default()
+        })
+        .unwrap();
+
+      let create = server
+        .mock("POST", "/submissions?base64_encoded=false&wait=false")
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{ "token": "a" }"#)
+        .create();
+
+      let poll = server
+        .mock(
+          "GET",
+          "/submissions/a?base64_encoded=false&wait=false&fields=*",
+        )
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(format!(r#"{{ "status": {status} }}"#))
+        .create();
+
+      assert_eq!(
+        client
+          .run_and_check(Submission {
+            source_code: "print(input())".into(),
+            language_id: 71,
+            stdin: Some("foo".into()),
+            expected_output: Some("foo".into()),
+            ..Default::default()
+          })
+          .await
+          .unwrap(),
+        passed
+      );
+
+      create.assert();
+      poll.assert();
+    }
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn run_batch_ok() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        poll_interval: Duration::ZERO,
+        ..Default::default()
+      })
+      .unwrap();
+
+    let create = server
+      .mock("POST", "/submissions/batch?base64_encoded=false")
+      .match_body(mockito::Matcher::PartialJsonString(
+        r#"{
+          "submissions": [
+            { "source_code": "print(1)", "language_id": 71 },
+            { "source_code": "print(2)", "language_id": 71 }
+          ]
+        }"#
+          .into(),
+      ))
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(r#"[{ "token": "a" }, { "token": "b" }]"#)
+      .create();
+
+    let queued = server
+      .mock(
+        "GET",
+        "/submissions/batch?tokens=a,b&base64_encoded=false&fields=*",
+      )
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{
+          "submissions": [
+            { "token": "a", "status": { "id": 1, "description": "In Queue" } },
+            { "token": "b", "status": { "id": 1, "description": "In Queue" } }
+          ]
+        }"#,
+      )
+      .expect(1)
+      .create();
+
+    let finished = server
+      .mock(
+        "GET",
+        "/submissions/batch?tokens=a,b&base64_encoded=false&fields=*",
+      )
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{
+          "submissions": [
+            { "token": "a", "stdout": "1\n", "status": { "id": 3, "description": "Accepted" } },
+            { "token": "b", "stdout": "2\n", "status": { "id": 3, "description": "Accepted" } }
+          ]
+        }"#,
+      )
+      .create();
+
+    let results = client
+      .run_batch(vec![
+        Submission {
+          source_code: "print(1)".into(),
+          language_id: 71,
+          ..Default::default()
+        },
+        Submission {
+          source_code: "print(2)".into(),
+          language_id: 71,
+          ..Default::default()
+        },
+      ])
+      .await
+      .unwrap();
+
+    assert_eq!(
+      results
+        .iter()
+        .map(|submission| submission.stdout.as_deref())
+        .collect::<Vec<Option<&str>>>(),
+      vec![Some("1\n"), Some("2\n")]
+    );
+
+    create.assert();
+    queued.assert();
+    finished.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn language_not_found() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mock = server
+      .mock("GET", "/languages/9000")
+      .with_status(404)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "error": "language not found" }"#)
+      .create();
+
+    let error = client.get_language(9000).await.unwrap_err();
+
+    assert_eq!(error.status_code(), Some(404));
+
+    assert!(matches!(
+      error,
+      Error::Api { status: 404, body } if body.contains("language not found")
+    ));
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn list_submissions_ok() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let body = r#"{
+      "submissions": [
+        { "token": "a", "status": { "id": 3, "description": "Accepted" } },
+        { "token": "b", "status": { "id": 5, "description": "Time Limit Exceeded" } }
+      ],
+      "meta": {
+        "current_page": 1,
+        "next_page": 2,
+        "prev_page": null,
+        "total_pages": 2,
+        "total_count": 4
+      }
+    }"#;
+
+    let mock = server
+      .mock(
+        "GET",
+        "/submissions?base64_encoded=false&fields=*&page=1&per_page=2",
+      )
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(body)
+      .create();
+
+    let page = client.list_submissions(1, 2, None).await.unwrap();
+
+    assert_eq!(page.submissions.len(), 2);
+    assert_eq!(page.meta.next_page, Some(2));
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn list_submissions_filtered_by_status() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let body = r#"{
+      "submissions": [
+        { "token": "a", "status": { "id": 3, "description": "Accepted" } },
+        { "token": "b", "status": { "id": 5, "description": "Time Limit Exceeded" } }
+      ],
+      "meta": {
+        "current_page": 1,
+        "next_page": null,
+        "prev_page": null,
+        "total_pages": 1,
+        "total_count": 2
+      }
+    }"#;
+
+    let mock = server
+      .mock(
+        "GET",
+        "/submissions?base64_encoded=false&fields=*&page=1&per_page=20&status_id=5",
+      )
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(body)
+      .create();
+
+    let page = client.list_submissions(1, 20, Some(5)).await.unwrap();
+
+    assert_eq!(
+      page
+        .submissions
+        .iter()
+        .map(|submission| submission.token.as_deref().unwrap())
+        .collect::<Vec<&str>>(),
+      vec!["b"]
+    );
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn get_submission_requested_fields() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mock = server
+      .mock(
+        "GET",
+        "/submissions/a?base64_encoded=false&wait=false&fields=stdout,status",
+      )
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{
+          "stdout": null,
+          "status": { "id": 3, "description": "Accepted" }
+        }"#,
+      )
+      .create();
+
+    let submission = client
+      .get_submission(
+        "a",
+        Some(&[SubmissionField::Stdout, SubmissionField::Status]),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(submission.stdout, None);
+    assert!(submission.was_fetched("stdout"));
+
+    assert_eq!(submission.stderr, None);
+    assert!(!submission.was_fetched("stderr"));
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn get_submission_partial() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mock = server
+      .mock(
+        "GET",
+        "/submissions/a?base64_encoded=false&wait=false&fields=stdout,status",
+      )
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{
+          "stdout": "hello\n",
+          "status": { "id": 3, "description": "Accepted" }
+        }"#,
+      )
+      .create();
+
+    let submission = client
+      .get_submission_partial(
+        "a",
+        &[SubmissionField::Stdout, SubmissionField::Status],
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(
+      submission.fields(),
+      [SubmissionField::Stdout, SubmissionField::Status]
+    );
+
+    assert_eq!(
+      submission.get::<String>(SubmissionField::Stdout).unwrap(),
+      Some("hello\n".into())
+    );
+
+    assert_eq!(
+      submission
+        .get::<Status>(SubmissionField::Status)
+        .unwrap()
+        .map(|status| status.id),
+      Some(3)
+    );
+
+    assert!(matches!(
+      submission.get::<String>(SubmissionField::Stderr),
+      Err(Error::FieldNotFetched(field)) if field == "stderr"
+    ));
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn get_submission_all_fields() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mock = server
+      .mock(
+        "GET",
+        "/submissions/a?base64_encoded=false&wait=false&fields=*",
+      )
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "stdout": "hello\n", "stderr": null }"#)
+      .create();
+
+    let submission = client.get_submission("a", None).await.unwrap();
+
+    assert_eq!(submission.stdout.as_deref(), Some("hello\n"));
+    assert!(submission.was_fetched("stderr"));
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn get_submission_raw() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let body = r#"{
+      "stdout": "hello\n",
+      "time": "0.001",
+      "status": { "id": 3, "description": "Accepted" },
+      "unmodeled": { "nested": [1, 2] }
+    }"#;
+
+    let mock = server
+      .mock(
+        "GET",
+        "/submissions/a?base64_encoded=false&wait=false&fields=*",
+      )
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(body)
+      .create();
+
+    assert_eq!(
+      client.get_submission_raw("a", None).await.unwrap(),
+      serde_json::from_str::<Value>(body).unwrap()
+    );
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn get_submission_base64_encoded() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        base64_encoded: true,
+        ..Default::default()
+      })
+      .unwrap();
+
+    let mock = server
+      .mock(
+        "GET",
+        "/submissions/a?base64_encoded=true&wait=false&fields=*",
+      )
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{
+          "source_code": "cHJpbnQoaW5wdXQoKSk=\n",
+          "stdin": "aGVsbG8=\n",
+          "expected_output": "aGVsbG8K\n",
+          "stdout": "aGVsbG8K\n",
+          "stderr": null
+        }"#,
+      )
+      .create();
+
+    let submission = client.get_submission("a", None).await.unwrap();
+
+    assert_eq!(submission.source_code, "print(input())");
+    assert_eq!(submission.stdin.as_deref(), Some("hello"));
+    assert_eq!(submission.expected_output.as_deref(), Some("hello\n"));
+    assert_eq!(submission.stdout.as_deref(), Some("hello\n"));
+    assert_eq!(submission.stderr, None);
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn get_submission_base64_binary_output() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        base64_encoded: true,
+        ..Default::default()
+      })
+      .unwrap();
+
+    let mock = server
+      .mock(
+        "GET",
+        "/submissions/a?base64_encoded=true&wait=false&fields=*",
+      )
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "stdout": "aGn//go=\n", "stderr": "bm90IGJhc2U2NA=" }"#)
+      .create();
+
+    assert!(matches!(
+      client.get_submission("a", None).await,
+      Err(Error::Base64(field)) if field == "stderr"
+    ));
+
+    let mock_ok = server
+      .mock(
+        "GET",
+        "/submissions/b?base64_encoded=true&wait=false&fields=*",
+      )
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "stdout": "aGn//go=\n" }"#)
+      .create();
+
+    let submission = client.get_submission("b", None).await.unwrap();
+
+    assert_eq!(submission.stdout.as_deref(), Some("hi\u{fffd}\u{fffd}\n"));
+
+    mock.assert();
+    mock_ok.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn deserialize_error_context() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mock = server
+      .mock(
+        "GET",
+        "/submissions/a?base64_encoded=false&wait=false&fields=*",
+      )
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "stdout": "hello\n", "status": "#)
+      .create();
+
+    match client.get_submission("a", None).await {
+      Err(Error::Deserialize {
+        endpoint, snippet, ..
+      }) => {
+        assert_eq!(
+          endpoint,
+          "/submissions/a?base64_encoded=false&wait=false&fields=*"
+        );
+        assert_eq!(snippet, r#"{ "stdout": "hello\n", "status": "#);
+      }
+      result => panic!("expected a deserialize error, got {result:?}"),
+    }
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn response_too_large() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        max_response_bytes: Some(16),
+        ..Default::default()
+      })
+      .unwrap();
+
+    let mock = server
+      .mock("GET", "/languages")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_chunked_body(|writer| {
+        writer
+          .write_all(br#"[{ "id": 45, "name": "Assembly (NASM 2.14.02)" }]"#)
+      })
+      .create();
+
+    assert!(matches!(
+      client.get_languages().await,
+      Err(Error::ResponseTooLarge { limit: 16 })
+    ));
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn response_within_limit() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        max_response_bytes: Some(1024),
+        ..Default::default()
+      })
+      .unwrap();
+
+    let mock = server
+      .mock("GET", "/languages")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(r#"[{ "id": 45, "name": "Assembly (NASM 2.14.02)" }]"#)
+      .create();
+
+    assert_eq!(client.get_languages().await.unwrap().len(), 1);
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn require_both_auth_missing_token() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        authentication_token: Some("token".into()),
+        require_both_auth: true,
+        ..Default::default()
+      })
+      .unwrap();
+
+    let mock = server.mock("GET", "/languages").expect(0).create();
+
+    assert!(matches!(
+      client.get_languages().await,
+      Err(Error::MissingAuth(header)) if header == "X-Auth-User"
+    ));
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn require_both_auth_ok() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        authentication_token: Some("foo".into()),
+        authorization_token: Some("bar".into()),
+        require_both_auth: true,
+        ..Default::default()
+      })
+      .unwrap();
+
+    let mock = server
+      .mock("GET", "/languages")
+      .match_header("X-Auth-Token", "foo")
+      .match_header("X-Auth-User", "bar")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body("[]")
+      .create();
+
+    assert!(client.get_languages().await.unwrap().is_empty());
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn languages_by_ids() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mocks = [
+      server
+        .mock("GET", "/languages/45")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{ "id": 45, "name": "Assembly (NASM 2.14.02)" }"#)
+        .create(),
+      server
+        .mock("GET", "/languages/9000")
+        .with_status(404)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{ "error": "language not found" }"#)
+        .create(),
+      server
+        .mock("GET", "/languages/46")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{ "id": 46, "name": "Bash (5.0.0)" }"#)
+        .create(),
+    ];
+
+    let languages = client.get_languages_by_ids(&[45, 9000, 46]).await.unwrap();
+
+    assert_eq!(languages.len(), 3);
+    assert_eq!(languages[0].as_ref().unwrap().id, 45);
+    assert_eq!(languages[1].as_ref().unwrap_err().status_code(), Some(404));
+    assert_eq!(languages[2].as_ref().unwrap().id, 46);
+
+    for mock in mocks {
+      mock.assert();
+    }
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn wait_for_submission_ok() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let endpoint = "/submissions/a?base64_encoded=false&wait=false&fields=*";
+
+    let processing = server
+      .mock("GET", endpoint)
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "status": { "id": 2, "description": "Processing" } }"#)
+      .expect(1)
+      .create();
+
+    let accepted = server
+      .mock("GET", endpoint)
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "status": { "id": 3, "description": "Accepted" } }"#)
+      .create();
+
+    let submission = client
+      .wait_for_submission("a", Duration::ZERO, Duration::from_secs(5))
+      .await
+      .unwrap();
+
+    assert_eq!(submission.status.unwrap().id, 3);
+
+    processing.assert();
+    accepted.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn wait_until_status() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let endpoint = "/submissions/a?base64_encoded=false&wait=false&fields=*";
+
+    let mocks = [
+      r#"{ "status": { "id": 1, "description": "In Queue" } }"#,
+      r#"{ "status": { "id": 2, "description": "Processing" } }"#,
+      r#"{ "status": { "id": 1, "description": "In Queue" } }"#,
+      r#"{ "status": { "id": 3, "description": "Accepted" } }"#,
+    ]
+    .map(|body| {
+      server
+        .mock("GET", endpoint)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(body)
+        .expect(1)
+        .create()
+    });
+
+    for expected in [2, 3] {
+      let submission = client
+        .wait_until_status("a", 2, Duration::ZERO, Duration::from_secs(5))
+        .await
+        .unwrap();
+
+      assert_eq!(submission.status.unwrap().id, expected);
+    }
+
+    for mock in mocks {
+      mock.assert();
+    }
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn wait_for_submission_timeout() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mock = server
+      .mock(
+        "GET",
+        "/submissions/a?base64_encoded=false&wait=false&fields=*",
+      )
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "status": { "id": 1, "description": "In Queue" } }"#)
+      .expect_at_least(1)
+      .create();
+
+    assert!(matches!(
+      client
+        .wait_for_submission(
+          "a",
+          Duration::from_millis(10),
+          Duration::from_millis(50)
+        )
+        .await,
+      Err(Error::Timeout)
+    ));
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn wait_for_submission_dropped() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mock = server
+      .mock(
+        "GET",
+        "/submissions/a?base64_encoded=false&wait=false&fields=*",
+      )
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "status": { "id": 1, "description": "In Queue" } }"#)
+      .expect(1)
+      .create();
+
+    let wait = tokio::spawn(async move {
+      client
+        .wait_for_submission(
+          "a",
+          Duration::from_millis(200),
+          Duration::from_secs(5),
+        )
+        .await
+    });
+
+    time::timeout(Duration::from_secs(5), async {
+      while !mock.matched() {
+        sleep(Duration::from_millis(10)).await;
+      }
+    })
+    .await
+    .unwrap();
+
+    wait.abort();
+
+    assert!(wait.await.unwrap_err().is_cancelled());
+
+    // A poll left running after the abort would hit the mock again within
+    // one interval, which `expect(1)` rejects.
+    sleep(Duration::from_millis(1000)).await;
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn create_submission_fire_and_forget() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        wait: true,
+        ..Default::default()
+      })
+      .unwrap();
+
+    let mock = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{
+          "token": "d85cd024-1548-4165-96c7-7bc88673f194",
+          "status": "not a status object"
+        }"#,
+      )
+      .create();
+
+    let token = client
+      .create_submission_fire_and_forget(Submission {
+        source_code: r#"print("Hello, world!")"#.into(),
+        language_id: 71,
+        callback_url: Some("https://example.com/callback".into()),
+        ..Default::default()
+      })
+      .await
+      .unwrap();
+
+    assert_eq!(
+      token,
+      Token {
+        token: "d85cd024-1548-4165-96c7-7bc88673f194".into()
+      }
+    );
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn list_all_submissions_ok() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let first = server
+      .mock(
+        "GET",
+        "/submissions?base64_encoded=false&fields=*&page=1&per_page=100",
+      )
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{
+          "submissions": [{ "token": "a" }, { "token": "b" }],
+          "meta": {
+            "current_page": 1,
+            "next_page": 2,
+            "prev_page": null,
+            "total_pages": 2,
+            "total_count": 3
+          }
+        }"#,
+      )
+      .create();
+
+    let second = server
+      .mock(
+        "GET",
+        "/submissions?base64_encoded=false&fields=*&page=2&per_page=100",
+      )
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{
+          "submissions": [{ "token": "c" }],
+          "meta": {
+            "current_page": 2,
+            "next_page": null,
+            "prev_page": 1,
+            "total_pages": 2,
+            "total_count": 3
+          }
+        }"#,
+      )
+      .create();
+
+    assert_eq!(client.list_all_submissions().await.unwrap().len(), 3);
+
+    first.assert();
+    second.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn list_submissions_cursor() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let first = server
+      .mock(
+        "GET",
+        "/submissions?base64_encoded=false&fields=*&per_page=100",
+      )
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{
+          "submissions": [{ "token": "a" }, { "token": "b" }],
+          "meta": { "next": "c/2" }
+        }"#,
+      )
+      .create();
+
+    let second = server
+      .mock(
+        "GET",
+        "/submissions?base64_encoded=false&fields=*&per_page=100&cursor=c%2F2",
+      )
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{
+          "submissions": [{ "token": "c" }],
+          "meta": { "next": null }
+        }"#,
+      )
+      .create();
+
+    let mut tokens = Vec::new();
+
+    let mut cursor = None;
+
+    loop {
+      let (submissions, next) = client
+        .list_submissions_cursor(cursor.as_deref())
+        .await
+        .unwrap();
+
+      tokens.extend(
+        submissions
+          .into_iter()
+          .filter_map(|submission| submission.token),
+      );
+
+      match next {
+        Some(next) => cursor = Some(next),
+        None => break,
+      }
+    }
+
+    assert_eq!(tokens, ["a", "b", "c"]);
+
+    first.assert();
+    second.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn submissions_by_language() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mock = server
+      .mock(
+        "GET",
+        "/submissions?base64_encoded=false&fields=*&page=1&per_page=100",
+      )
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{
+          "submissions": [
+            { "token": "a", "language_id": 71 },
+            { "token": "b", "language_id": 54 },
+            { "token": "c", "language_id": 71 },
+            { "token": "d" }
+          ],
+          "meta": {
+            "current_page": 1,
+            "next_page": null,
+            "prev_page": null,
+            "total_pages": 1,
+            "total_count": 4
+          }
+        }"#,
+      )
+      .create();
+
+    let groups = client.submissions_by_language().await.unwrap();
+
+    assert_eq!(groups.len(), 3);
+    assert_eq!(groups[&71].len(), 2);
+    assert_eq!(groups[&54].len(), 1);
+    assert_eq!(groups[&0][0].token.as_deref(), Some("d"));
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn delete_submissions_where() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mut mocks = Vec::new();
+
+    for (page, submissions, next_page) in [
+      (
+        1,
+        r#"[
+          { "token": "a", "created_at": "2021-01-01T00:00:00.000Z" },
+          { "token": "b", "created_at": "2023-01-01T00:00:00.000Z" }
+        ]"#,
+        "2",
+      ),
+      (
+        2,
+        r#"[{ "token": "c", "created_at": "2021-06-01T00:00:00.000Z" }]"#,
+        "null",
+      ),
+    ] {
+      mocks.push(
+        server
+          .mock(
+            "GET",
+            format!(
+              "/submissions?base64_encoded=false&fields=*&page={page}&per_page=100"
+            )
+            .as_str(),
+          )
+          .with_status(200)
+          .with_header("content-type", "application/json")
+          .with_body(format!(
+            r#"{{
+              "submissions": {submissions},
+              "meta": {{
+                "current_page": {page},
+                "next_page": {next_page},
+                "prev_page": null,
+                "total_pages": 2,
+                "total_count": 3
+              }}
+            }}"#
+          ))
+          .create(),
+      );
+    }
+
+    for token in ["a", "c"] {
+      mocks.push(
+        server
+          .mock(
+            "DELETE",
+            format!("/submissions/{token}?fields=token").as_str(),
+          )
+          .with_status(200)
+          .with_header("content-type", "application/json")
+          .with_body(format!(r#"{{ "token": "{token}" }}"#))
+          .expect(1)
+          .create(),
+      );
+    }
+
+    let kept = server
+      .mock("DELETE", "/submissions/b?fields=token")
+      .expect(0)
+      .create();
+
+    let cutoff = Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap();
+
+    let deleted = client
+      .delete_submissions_where(|submission| {
+        submission
+          .created_at
+          .is_some_and(|created_at| created_at < cutoff)
+      })
+      .await
+      .unwrap();
+
+    assert_eq!(deleted, 2);
+
+    for mock in mocks {
+      mock.assert();
+    }
+
+    kept.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn list_all_submissions_max_pages() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        max_pages: 1,
+        ..Default::default()
+      })
+      .unwrap();
+
+    let first = server
+      .mock(
+        "GET",
+        "/submissions?base64_encoded=false&fields=*&page=1&per_page=100",
+      )
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{
+          "submissions": [{ "token": "a" }, { "token": "b" }],
+          "meta": {
+            "current_page": 1,
+            "next_page": 2,
+            "prev_page": null,
+            "total_pages": 2,
+            "total_count": 3
+          }
+        }"#,
+      )
+      .create();
+
+    assert_eq!(client.list_all_submissions().await.unwrap().len(), 2);
+
+    first.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn is_paused() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let paused = server
+      .mock("GET", "/workers")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"[
+          { "queue": "default", "size": 3, "available": 2, "idle": 0, "working": 0, "paused": 2, "failed": 0 },
+          { "queue": "heavy", "size": 0, "available": 1, "idle": 0, "working": 0, "paused": 1, "failed": 0 }
+        ]"#,
+      )
+      .expect(1)
+      .create();
+
+    assert!(client.is_paused().await.unwrap());
+
+    let running = server
+      .mock("GET", "/workers")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"[
+          { "queue": "default", "size": 3, "available": 2, "idle": 0, "working": 1, "paused": 1, "failed": 0 },
+          { "queue": "heavy", "size": 0, "available": 1, "idle": 0, "working": 0, "paused": 1, "failed": 0 }
+        ]"#,
+      )
+      .create();
+
+    assert!(!client.is_paused().await.unwrap());
+
+    paused.assert();
+    running.assert();
+  }
+
+  #[test]
+  fn poll_jitter() {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    let interval = Duration::from_millis(1000);
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let delays = (0..10)
+      .map(|_| jitter(interval, &mut rng))
+      .collect::<Vec<Duration>>();
+
+    assert!(delays.iter().all(|delay| {
+      *delay >= Duration::from_millis(500)
+        && *delay <= Duration::from_millis(1500)
+    }));
+
+    assert!(delays.windows(2).any(|pair| pair[0] != pair[1]));
+
+    let client = Client::new("http://localhost:2358");
+
+    assert_eq!(client.poll_delay(interval), interval);
+
+    let client = Client::new("http://localhost:2358")
+      .configure(Config {
+        poll_jitter: true,
+        ..Default::default()
+      })
+      .unwrap();
+
+    assert!(client.poll_delay(interval) <= Duration::from_millis(1500));
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn server_error_plain_text() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
+
+    let mock = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .with_status(500)
+      .with_header("content-type", "text/plain")
+      .with_body("NoMethodError: undefined method `id' for nil:NilClass")
+      .create();
+
+    let result = client
+      .create_submission(Submission {
+        source_code: r#"print("Hello, world!")"#.into(),
+        language_id: 71,
+        ..Default::default()
+      })
+      .await;
+
+    assert!(matches!(
+      result,
+      Err(Error::Api { status: 500, body }) if body.starts_with("NoMethodError")
+    ));
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn server_error_json() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url());
 
     let mock = server
-      .mock("GET", "/languages/1")
-      .with_status(200)
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .with_status(503)
       .with_header("content-type", "application/json")
-      .with_body(body)
+      .with_body(r#"{ "error": "unavailable" }"#)
       .create();
 
-    let language = client.get_language(1).await.unwrap();
+    let result = client
+      .create_submission(Submission {
+        source_code: r#"print("Hello, world!")"#.into(),
+        language_id: 71,
+        ..Default::default()
+      })
+      .await;
 
-    assert_eq!(language, serde_json::from_str::<Language>(body).unwrap());
+    assert_eq!(result.unwrap_err().status_code(), Some(503));
 
     mock.assert();
   }
 
   #[tokio::test(flavor = "multi_thread")]
-  async fn about_ok() {
+  async fn server_error_body_truncated() {
     let TestContext { mut server } = TestContext::new().await;
 
     let client = Client::new(&server.url());
 
-    let body = r#"{
-      "version": "1.5.0",
-      "homepage": "https://judge0.com",
-      "source_code": "https://github.com/judge0/judge0",
-      "maintainer": "Herman Zvonimir Došilović <hermanz.dosilovic@gmail.com>"
-    }"#;
-
     let mock = server
       .mock("GET", "/about")
-      .with_status(200)
-      .with_header("content-type", "application/json")
-      .with_body(body)
+      .with_status(500)
+      .with_header("content-type", "text/html")
+      .with_body("é".repeat(MAX_ERROR_BODY_BYTES))
       .create();
 
-    let about = client.get_about().await.unwrap();
+    let Err(Error::Api { status, body }) = client.get_about().await else {
+      panic!("expected an api error");
+    };
 
-    assert_eq!(about, serde_json::from_str::<About>(body).unwrap());
+    assert_eq!(status, 500);
+    assert_eq!(body.len(), MAX_ERROR_BODY_BYTES);
 
     mock.assert();
   }
 
   #[tokio::test(flavor = "multi_thread")]
-  async fn workers_ok() {
+  async fn create_submission_safe_non_utf8() {
     let TestContext { mut server } = TestContext::new().await;
 
     let client = Client::new(&server.url());
 
-    let body = r#"[{
-      "queue": "default",
-      "size": 0,
-      "available": 1,
-      "idle": 1,
-      "working": 0,
-      "paused": 0,
-      "failed": 0
-    }]"#;
+    let source_code = String::from_utf8_lossy(b"print(\"\xFE\")").into_owned();
 
     let mock = server
-      .mock("GET", "/workers")
-      .with_status(200)
+      .mock("POST", "/submissions?base64_encoded=true&wait=false")
+      .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+        "source_code": STANDARD.encode(&source_code),
+      })))
+      .with_status(201)
       .with_header("content-type", "application/json")
-      .with_body(body)
+      .with_body(r#"{ "token": "fcd0de6d-ee52-4a9d-8a00-6e0d98d394cf" }"#)
       .create();
 
-    let workers = client.get_workers().await.unwrap();
+    let token = client
+      .create_submission_safe(Submission {
+        source_code,
+        language_id: 70,
+        ..Default::default()
+      })
+      .await
+      .unwrap();
 
-    assert_eq!(workers, serde_json::from_str::<Vec<Worker>>(body).unwrap());
+    assert_eq!(token.token, "fcd0de6d-ee52-4a9d-8a00-6e0d98d394cf");
 
     mock.assert();
   }
 
   #[tokio::test(flavor = "multi_thread")]
-  async fn create_submission_ok() {
+  async fn create_submission_safe_utf8() {
     let TestContext { mut server } = TestContext::new().await;
 
     let client = Client::new(&server.url());
 
-    let body = r#"{
-      "token": "d85cd024-1548-4165-96c7-7bc88673f194"
-    }"#;
-
     let mock = server
       .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .match_body(mockito::Matcher::PartialJsonString(
+        r#"{ "stdin": "héllo\n" }"#.into(),
+      ))
       .with_status(201)
       .with_header("content-type", "application/json")
-      .with_body(body)
+      .with_body(r#"{ "token": "fcd0de6d-ee52-4a9d-8a00-6e0d98d394cf" }"#)
       .create();
 
-    let result = client
-      .create_submission(Submission {
-        source_code: r#"print("Hello, world!")"#.into(),
-        language_id: 1,
+    client
+      .create_submission_safe(Submission {
+        source_code: "print(input())".into(),
+        language_id: 71,
+        stdin: Some("héllo\n".into()),
         ..Default::default()
       })
       .await
       .unwrap();
 
-    assert_eq!(result, serde_json::from_str::<Value>(body).unwrap());
-
     mock.assert();
   }
 
   #[tokio::test(flavor = "multi_thread")]
-  async fn create_submission_invalid_language() {
+  async fn follow_redirects() {
     let TestContext { mut server } = TestContext::new().await;
 
-    let client = Client::new(&server.url());
-
-    let body = r#"{
-      "language_id": ["language with id 9000 doesn't exist"]
-    }"#;
+    let redirect = server
+      .mock("GET", "/languages")
+      .with_status(301)
+      .with_header("location", &format!("{}/v2/languages", server.url()))
+      .expect(2)
+      .create();
 
-    let mock = server
-      .mock("POST", "/submissions?base64_encoded=false&wait=false")
-      .with_status(422)
+    let target = server
+      .mock("GET", "/v2/languages")
+      .with_status(200)
       .with_header("content-type", "application/json")
-      .with_body(body)
+      .with_body(r#"[{ "id": 45, "name": "Assembly (NASM 2.14.02)" }]"#)
+      .expect(1)
       .create();
 
-    let result = client
-      .create_submission(Submission {
-        source_code: r#"print("Hello, world!")"#.into(),
-        language_id: 9000,
+    let client = Client::new(&server.url())
+      .configure(Config::default())
+      .unwrap();
+
+    assert_eq!(client.get_languages().await.unwrap().len(), 1);
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        follow_redirects: false,
         ..Default::default()
       })
-      .await
       .unwrap();
 
-    assert_eq!(result, serde_json::from_str::<Value>(body).unwrap());
+    assert_eq!(
+      client.get_languages().await.unwrap_err().status_code(),
+      Some(301)
+    );
 
-    mock.assert();
+    redirect.assert();
+    target.assert();
   }
 
   #[tokio::test(flavor = "multi_thread")]
-  async fn create_submission_invalid_wall_time_limit() {
-    let TestContext { mut server } = TestContext::new().await;
+  async fn connect_timeout() {
+    let client = Client::new("http://10.255.255.1:2358")
+      .configure(Config {
+        connect_timeout: Some(Duration::from_millis(100)),
+        ..Default::default()
+      })
+      .unwrap();
 
-    let client = Client::new(&server.url());
+    let start = Instant::now();
 
-    let body = r#"{
-      "wall_time_limit": ["must be less than or equal to 150"]
-    }"#;
+    let error = client.get_about().await.unwrap_err();
+
+    assert!(matches!(error, Error::Request(_)));
+    assert!(start.elapsed() < Duration::from_secs(5));
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn request_deadline() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        request_deadline: Some(Duration::from_millis(50)),
+        ..Default::default()
+      })
+      .unwrap();
 
     let mock = server
-      .mock("POST", "/submissions?base64_encoded=false&wait=false")
-      .with_status(422)
+      .mock("GET", "/languages")
+      .with_status(200)
       .with_header("content-type", "application/json")
-      .with_body(body)
+      .with_chunked_body(|writer| {
+        std::thread::sleep(Duration::from_millis(500));
+        writer.write_all(b"[]")
+      })
       .create();
 
-    let result = client
-      .create_submission(Submission {
-        source_code: r#"print("Hello, world!")"#.into(),
-        language_id: 4,
-        number_of_runs: Some(1),
-        stdin: Some("Judge0".into()),
-        expected_output: Some("hello, Judge0".into()),
-        cpu_time_limit: Some(1.0),
-        cpu_extra_time: Some(0.5),
-        wall_time_limit: Some(100000.0),
-        memory_limit: Some(128000.0),
-        stack_limit: Some(128000),
-        enable_per_process_and_thread_time_limit: Some(false),
-        enable_per_process_and_thread_memory_limit: Some(false),
-        max_file_size: Some(1024),
+    let start = Instant::now();
+
+    assert!(matches!(client.get_languages().await, Err(Error::Timeout)));
+
+    assert!(start.elapsed() < Duration::from_millis(500));
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn request_deadline_multipart() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        request_deadline: Some(Duration::from_millis(50)),
+        use_multipart: true,
         ..Default::default()
       })
-      .await
       .unwrap();
 
-    assert_eq!(result, serde_json::from_str::<Value>(body).unwrap());
+    let _mock = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_chunked_body(|writer| {
+        std::thread::sleep(Duration::from_millis(500));
+        writer.write_all(br#"{ "token": "a" }"#)
+      })
+      .create();
+
+    let start = Instant::now();
 
-    mock.assert();
+    assert!(matches!(
+      client
+        .create_submission(Submission {
+          source_code: "print(1)".into(),
+          language_id: 71,
+          ..Default::default()
+        })
+        .await,
+      Err(Error::Timeout)
+    ));
+
+    assert!(start.elapsed() < Duration::from_millis(500));
   }
 
   #[tokio::test(flavor = "multi_thread")]
-  async fn create_submission_invalid_utf8() {
+  async fn resume_batch() {
     let TestContext { mut server } = TestContext::new().await;
 
-    let client = Client::new(&server.url()).configure(Config {
-      wait: true,
-      ..Default::default()
-    });
+    let client = Client::new(&server.url());
 
-    let body = r#"{
-      "token": "fcd0de6d-ee52-4a9d-8a00-6e0d98d394cf",
-      "error": "some attributes for this submission cannot be converted to UTF-8, use base64_encoded=true query parameter"
-    }"#;
+    let endpoint =
+      "/submissions/batch?tokens=a,b&base64_encoded=false&fields=*";
 
-    let mock = server
-      .mock("POST", "/submissions?base64_encoded=false&wait=true")
-      .with_status(201)
+    let processing = server
+      .mock("GET", endpoint)
+      .with_status(200)
       .with_header("content-type", "application/json")
-      .with_body(body)
+      .with_body(
+        r#"{
+          "submissions": [
+            { "token": "a", "status": { "id": 3, "description": "Accepted" } },
+            { "token": "b", "status": { "id": 1, "description": "In Queue" } }
+          ]
+        }"#,
+      )
+      .expect(1)
       .create();
 
-    let result = client
-      .create_submission(Submission {
-        source_code: r#""print(\"\xFE\")""#.into(),
-        language_id: 70,
-        ..Default::default()
-      })
+    let finished = server
+      .mock("GET", endpoint)
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{
+          "submissions": [
+            { "token": "a", "status": { "id": 3, "description": "Accepted" } },
+            { "token": "b", "status": { "id": 3, "description": "Accepted" } }
+          ]
+        }"#,
+      )
+      .create();
+
+    let saved = serde_json::to_string(&[
+      Token { token: "a".into() },
+      Token { token: "b".into() },
+    ])
+    .unwrap();
+
+    let tokens = serde_json::from_str::<Vec<Token>>(&saved)
+      .unwrap()
+      .into_iter()
+      .map(|token| token.token)
+      .collect();
+
+    let submissions = client
+      .resume_batch(tokens, Duration::ZERO, Duration::from_secs(5))
       .await
       .unwrap();
 
-    assert_eq!(result, serde_json::from_str::<Value>(body).unwrap());
+    assert_eq!(submissions.len(), 2);
+    assert!(submissions.iter().all(Submission::is_finished));
 
-    mock.assert();
+    processing.assert();
+    finished.assert();
   }
 
   #[tokio::test(flavor = "multi_thread")]
-  async fn create_submission_wait_for_finish() {
+  async fn wait_for_batches() {
     let TestContext { mut server } = TestContext::new().await;
 
-    let client = Client::new(&server.url()).configure(Config {
-      wait: true,
-      ..Default::default()
-    });
+    let client = Client::new(&server.url());
 
-    let body = r#"{
-      "stdout": "hello, Judge0\n",
-      "time": "0.001",
-      "memory": 380,
-      "stderr": null,
-      "token": "eb0dd001-66db-47f4-8a69-b736c9bc23f6",
-      "compile_output": null,
-      "message": null,
-      "status": {
-        "id": 3,
-        "description": "Accepted"
+    let batch = |tokens: &str, statuses: &[usize]| {
+      let submissions = tokens
+        .split(',')
+        .zip(statuses)
+        .map(|(token, id)| {
+          format!(r#"{{ "token": "{token}", "status": {id} }}"#)
+        })
+        .collect::<Vec<String>>()
+        .join(",");
+
+      format!(r#"{{ "submissions": [{submissions}] }}"#)
+    };
+
+    let first = "/submissions/batch?tokens=a,b&base64_encoded=false&fields=*";
+
+    let second = "/submissions/batch?tokens=c&base64_encoded=false&fields=*";
+
+    let mocks = [
+      server
+        .mock("GET", first)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(batch("a,b", &[2, 3]))
+        .expect(2)
+        .create(),
+      server
+        .mock("GET", first)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(batch("a,b", &[3, 4]))
+        .create(),
+      server
+        .mock("GET", second)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(batch("c", &[6]))
+        .create(),
+    ];
+
+    let batches = client
+      .wait_for_batches(
+        vec![vec!["a".into(), "b".into()], vec!["c".into()]],
+        Duration::ZERO,
+        Duration::from_secs(5),
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(
+      batches
+        .iter()
+        .map(|batch| {
+          batch
+            .iter()
+            .map(|submission| submission.status.as_ref().unwrap().id)
+            .collect::<Vec<usize>>()
+        })
+        .collect::<Vec<_>>(),
+      [vec![3, 4], vec![6]]
+    );
+
+    for mock in mocks {
+      mock.assert();
+    }
+  }
+
+  #[test]
+  fn build_url() {
+    for base_url in ["http://localhost:2358", "http://localhost:2358/"] {
+      let client = Client::new(base_url);
+
+      for endpoint in ["/submissions?fields=*", "submissions?fields=*"] {
+        assert_eq!(
+          client.build_url(endpoint),
+          "http://localhost:2358/submissions?fields=*"
+        );
       }
-    }"#;
+    }
+
+    assert_eq!(
+      Client::new("https://example.com/judge0/").build_url("/about"),
+      "https://example.com/judge0/about"
+    );
+
+    for prefix in ["api/judge0", "/api/judge0", "/api/judge0/"] {
+      assert_eq!(
+        Client::new("http://localhost:2358/")
+          .configure(Config {
+            path_prefix: Some(prefix.into()),
+            ..Default::default()
+          })
+          .unwrap()
+          .build_url("/about"),
+        "http://localhost:2358/api/judge0/about"
+      );
+    }
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn path_prefix() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        path_prefix: Some("/api/judge0/".into()),
+        ..Default::default()
+      })
+      .unwrap();
 
     let mock = server
-      .mock("POST", "/submissions?base64_encoded=false&wait=true")
+      .mock("GET", "/api/judge0/languages")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(r#"[{ "id": 45, "name": "Assembly (NASM 2.14.02)" }]"#)
+      .create();
+
+    assert_eq!(client.get_languages().await.unwrap().len(), 1);
+
+    mock.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn submit_to_channel() {
+    let TestContext { mut server } = TestContext::new().await;
+
+    let client = Arc::new(
+      Client::new(&server.url())
+        .configure(Config {
+          poll_interval: Duration::ZERO,
+          dedup_cache_size: 8,
+          ..Default::default()
+        })
+        .unwrap(),
+    );
+
+    let create = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
       .with_status(201)
       .with_header("content-type", "application/json")
-      .with_body(body)
+      .with_body(r#"{ "token": "a" }"#)
+      .expect(1)
+      .create();
+
+    let poll = server
+      .mock("GET", "/submissions/a?base64_encoded=false&wait=false&fields=*")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{ "token": "a", "stdout": "1\n", "status": { "id": 3, "description": "Accepted" } }"#,
+      )
       .create();
 
+    let (tx, mut rx) = mpsc::channel(1);
+
+    client.submit_to_channel(
+      Submission {
+        source_code: "print(1)".into(),
+        language_id: 71,
+        ..Default::default()
+      },
+      tx,
+    );
+
+    let submission = rx.recv().await.unwrap().unwrap();
+
+    assert!(submission.is_finished());
+    assert_eq!(submission.stdout.as_deref(), Some("1\n"));
+
     let result = client
       .create_submission(Submission {
-        source_code: r#"
-          #include <stdio.h>
-
-          int main(void) {
-            char name[10];
-            scanf("%s", name);
-            printf("hello, %s\n", name);
-            return 0;
-          }"#
-          .into(),
-        language_id: 4,
-        stdin: Some("Judge0".into()),
-        expected_output: Some("hello, Judge0".into()),
+        source_code: "print(1)".into(),
+        language_id: 71,
         ..Default::default()
       })
       .await
       .unwrap();
 
-    assert_eq!(result, serde_json::from_str::<Value>(body).unwrap());
+    assert_eq!(result.token(), "a");
 
-    mock.assert();
+    create.assert();
+    poll.assert();
   }
 }
@@ -1,4 +1,6 @@
-#[derive(Debug)]
+use super::*;
+
+#[derive(Debug, Clone)]
 pub struct Config {
   /// X-Auth-Token is the default header field name, but administrators of the
   /// judge0 instance you are using can change this default field name.
@@ -18,9 +20,118 @@ pub struct Config {
   /// to be authorized to list all submissions on Judge0.
   pub authorization_token: Option<String>,
 
+  /// Value of the `Accept` header sent on every request, `application/json`
+  /// by default, so that proxies in front of judge0 don't respond with HTML.
+  /// Set to `None` to leave the header out.
+  pub accept: Option<String>,
+
+  /// Path the judge0 API is served under when it sits behind a reverse
+  /// proxy, e.g. `/api/judge0`, prepended to every endpoint. Leading and
+  /// trailing slashes are ignored.
+  pub path_prefix: Option<String>,
+
+  /// Token sent as `Authorization: Bearer <token>` on every request, for
+  /// gateways in front of judge0 that use standard bearer authentication.
+  /// This is sent in addition to any X-Auth headers.
+  pub bearer_token: Option<String>,
+
+  /// Randomize the delay between polls by up to half the poll interval in
+  /// either direction, so that many clients polling at once don't hit the
+  /// server in lockstep.
+  pub poll_jitter: bool,
+
+  /// Maximum size of a response body in bytes. Larger responses fail with
+  /// `Error::ResponseTooLarge` instead of being read into memory.
+  pub max_response_bytes: Option<usize>,
+
+  /// Maximum combined size in bytes of a submission's `source_code`,
+  /// `stdin` and `additional_files`. Larger submissions fail with
+  /// `Error::InputTooLarge` instead of being sent.
+  pub max_input_bytes: Option<usize>,
+
+  /// Maximum number of pages fetched by helpers that follow pagination, like
+  /// `Client::list_all_submissions`.
+  pub max_pages: usize,
+
+  /// How long to wait for a connection to the judge0 instance to be
+  /// established, so that requests to an unreachable host fail fast.
+  pub connect_timeout: Option<Duration>,
+
+  /// Hard limit on how long a single API call may take, including reading
+  /// and parsing the response. Calls that exceed it fail with
+  /// `Error::Timeout` wherever they are stuck.
+  pub request_deadline: Option<Duration>,
+
+  /// Follow redirects issued by the instance or a load balancer in front of
+  /// it, e.g. from http to https. Disable this to treat redirects as errors.
+  pub follow_redirects: bool,
+
+  /// Maximum number of redirects followed for a single request when
+  /// `follow_redirects` is set.
+  pub max_redirects: usize,
+
+  /// Number of recent submissions to remember by source, language and stdin,
+  /// so that `Client::create_submission` returns the cached result for an
+  /// identical submission instead of running it again. Disabled when zero.
+  /// Submissions with a `callback_url` are never deduplicated.
+  pub dedup_cache_size: usize,
+
+  /// Language used when creating a submission whose `language_id` is unset,
+  /// i.e. zero, for tools that only ever submit one language.
+  pub default_language_id: Option<usize>,
+
+  /// Compiler options by language id, used when creating a submission that
+  /// doesn't set its own `compiler_options`, e.g. `-std=c++20` for C++.
+  pub compiler_options: HashMap<usize, String>,
+
+  /// PEM encoded certificate to trust in addition to the system roots, for
+  /// instances served with a certificate from an internal CA. An invalid
+  /// certificate makes `ClientBuilder::build` and `Client::configure` fail
+  /// with `Error::Certificate`.
+  pub root_cert_pem: Option<Vec<u8>>,
+
+  /// How long to wait between polls when waiting for submissions to finish in
+  /// helpers like `Client::grade`.
+  pub poll_interval: Duration,
+
+  /// How long to keep polling before giving up with `Error::Timeout` in
+  /// helpers like `Client::grade`.
+  pub poll_timeout: Duration,
+
+  /// How many times `Client::create_submission` retries a submission
+  /// rejected with `Error::QueueFull`, waiting `poll_interval` before the
+  /// first retry and doubling the wait after each one.
+  pub queue_full_retries: usize,
+
+  /// Some secured instances require both the authentication and authorization
+  /// headers on every request. When set, requests fail with
+  /// `Error::MissingAuth` unless both tokens are configured.
+  pub require_both_auth: bool,
+
+  /// Gzip JSON request bodies and send them with `Content-Encoding: gzip`,
+  /// which saves bandwidth for large sources or additional files. Only
+  /// enable this if the instance, or a proxy in front of it, accepts
+  /// compressed requests.
+  pub compress_request: bool,
+
   /// Set to true if you want to send base64 encoded data to judge0.
   pub base64_encoded: bool,
 
+  /// Use the URL-safe base64 alphabet instead of the standard one when
+  /// encoding and decoding text fields, for modified instances that expect
+  /// it. Padding is used either way.
+  pub base64_url_safe: bool,
+
+  /// Send submissions as a multipart form instead of a JSON body. Some
+  /// gateways in front of judge0 limit the size of JSON bodies, which large
+  /// sources or additional files can exceed.
+  pub use_multipart: bool,
+
+  /// Send `wait=true` when creating batch submissions. Upstream judge0 only
+  /// supports waiting for single submissions and ignores this, so only set
+  /// it for instances that are known to support waiting on batches.
+  pub batch_wait: bool,
+
   /// Instead of checking submission status by making another request, you can
   /// set the wait query parameter to true which will enable you to get
   /// submission status immediately as part of response to the request you
@@ -31,6 +142,17 @@ pub struct Config {
   pub wait: bool,
 }
 
+impl Config {
+  /// The base64 engine for text fields, see `base64_url_safe`.
+  pub(crate) fn base64_engine(&self) -> &'static GeneralPurpose {
+    if self.base64_url_safe {
+      &URL_SAFE
+    } else {
+      &STANDARD
+    }
+  }
+}
+
 impl Default for Config {
   fn default() -> Self {
     Self {
@@ -38,7 +160,30 @@ impl Default for Config {
       authentication_token: None,
       authorization_header_name: String::from("X-Auth-User"),
       authorization_token: None,
+      accept: Some("application/json".into()),
+      path_prefix: None,
+      bearer_token: None,
+      require_both_auth: false,
       base64_encoded: false,
+      base64_url_safe: false,
+      batch_wait: false,
+      compress_request: false,
+      poll_jitter: false,
+      max_response_bytes: None,
+      max_input_bytes: None,
+      request_deadline: None,
+      max_pages: 100,
+      connect_timeout: None,
+      dedup_cache_size: 0,
+      default_language_id: None,
+      compiler_options: HashMap::new(),
+      root_cert_pem: None,
+      follow_redirects: true,
+      max_redirects: 10,
+      poll_interval: Duration::from_secs(1),
+      poll_timeout: Duration::from_secs(60),
+      queue_full_retries: 0,
+      use_multipart: false,
       wait: false,
     }
   }
@@ -0,0 +1,236 @@
+/// Judge0 language identifiers whose compiler output uses rustc's format.
+const RUST_LANGUAGE_IDS: &[usize] = &[73];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+  /// File the diagnostic points at.
+  pub file: String,
+
+  /// One-based line number.
+  pub line: usize,
+
+  /// One-based column number, if the compiler reported one.
+  pub column: Option<usize>,
+
+  /// Severity of the diagnostic.
+  pub severity: Severity,
+
+  /// Diagnostic message, without the location and severity.
+  pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+  Error,
+  Warning,
+  Note,
+}
+
+impl Severity {
+  fn parse(severity: &str) -> Option<Self> {
+    match severity {
+      "error" | "fatal error" => Some(Self::Error),
+      "warning" => Some(Self::Warning),
+      "note" => Some(Self::Note),
+      _ => None,
+    }
+  }
+}
+
+/// Extract structured diagnostics from a submission's `compile_output`.
+///
+/// Rust submissions are parsed as rustc output, and everything else as the
+/// `file:line:column: severity: message` format used by GCC and Clang. This
+/// is best-effort, lines that don't match are skipped.
+///
+/// ```rust
+/// use judge0_rs::parse_compiler_diagnostics;
+///
+/// let diagnostics = parse_compiler_diagnostics(
+///   "main.c:3:5: error: expected ';' before 'return'",
+///   50,
+/// );
+/// ```
+pub fn parse_compiler_diagnostics(
+  output: &str,
+  language_id: usize,
+) -> Vec<Diagnostic> {
+  if RUST_LANGUAGE_IDS.contains(&language_id) {
+    parse_rustc(output)
+  } else {
+    output.lines().filter_map(parse_gcc_line).collect()
+  }
+}
+
+/// Parse a `file:line[:column]: severity: message` line.
+fn parse_gcc_line(line: &str) -> Option<Diagnostic> {
+  let (location, rest) = line.split_once(": ")?;
+
+  let (severity, message) = rest.split_once(": ")?;
+
+  let (file, line, column) = parse_location(location)?;
+
+  Some(Diagnostic {
+    file,
+    line,
+    column,
+    severity: Severity::parse(severity)?,
+    message: message.trim().to_owned(),
+  })
+}
+
+/// Parse rustc diagnostics, where a `severity[code]: message` header is
+/// followed by a `--> file:line:column` location line.
+fn parse_rustc(output: &str) -> Vec<Diagnostic> {
+  let mut diagnostics = Vec::new();
+
+  let mut header = None;
+
+  for line in output.lines() {
+    if let Some(location) = line.trim_start().strip_prefix("--> ") {
+      if let (Some((severity, message)), Some((file, line, column))) =
+        (header.take(), parse_location(location.trim()))
+      {
+        diagnostics.push(Diagnostic {
+          file,
+          line,
+          column,
+          severity,
+          message,
+        });
+      }
+    } else if let Some((prefix, message)) = line.split_once(": ") {
+      let severity = prefix.split('[').next().unwrap_or(prefix);
+
+      if let Some(severity) = Severity::parse(severity) {
+        header = Some((severity, message.trim().to_owned()));
+      }
+    }
+  }
+
+  diagnostics
+}
+
+/// Parse a `file:line[:column]` location.
+fn parse_location(location: &str) -> Option<(String, usize, Option<usize>)> {
+  let parts = location.rsplitn(3, ':').collect::<Vec<&str>>();
+
+  match parts.as_slice() {
+    [column, line, file] => match (line.parse(), column.parse()) {
+      (Ok(line), Ok(column)) => Some(((*file).to_owned(), line, Some(column))),
+      (Err(_), Ok(line)) => Some((format!("{file}:{line}"), line, None)),
+      _ => None,
+    },
+    [line, file] => Some(((*file).to_owned(), line.parse().ok()?, None)),
+    _ => None,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn gcc() {
+    let output = "\
+main.c: In function 'main':
+main.c:4:5: warning: implicit declaration of function 'prinf' [-Wimplicit-function-declaration]
+    4 |     prinf(\"hello\");
+      |     ^~~~~
+main.c:5:1: error: expected ';' before '}' token
+    5 | }
+      | ^
+main.c:4:5: note: include '<stdio.h>'
+";
+
+    assert_eq!(
+      parse_compiler_diagnostics(output, 50),
+      vec![
+        Diagnostic {
+          file: "main.c".into(),
+          line: 4,
+          column: Some(5),
+          severity: Severity::Warning,
+          message: "implicit declaration of function 'prinf' [-Wimplicit-function-declaration]".into(),
+        },
+        Diagnostic {
+          file: "main.c".into(),
+          line: 5,
+          column: Some(1),
+          severity: Severity::Error,
+          message: "expected ';' before '}' token".into(),
+        },
+        Diagnostic {
+          file: "main.c".into(),
+          line: 4,
+          column: Some(5),
+          severity: Severity::Note,
+          message: "include '<stdio.h>'".into(),
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn gcc_without_column() {
+    assert_eq!(
+      parse_compiler_diagnostics(
+        "main.cpp:7: fatal error: iostream: No such file or directory",
+        54
+      ),
+      vec![Diagnostic {
+        file: "main.cpp".into(),
+        line: 7,
+        column: None,
+        severity: Severity::Error,
+        message: "iostream: No such file or directory".into(),
+      }]
+    );
+  }
+
+  #[test]
+  fn rustc() {
+    let output = "\
+warning: unused variable: `y`
+ --> main.rs:3:9
+  |
+3 |     let y = 1;
+  |         ^ help: if this is intentional, prefix it with an underscore: `_y`
+  |
+  = note: `#[warn(unused_variables)]` on by default
+
+error[E0425]: cannot find value `x` in this scope
+ --> main.rs:2:20
+  |
+2 |     println!(\"{}\", x);
+  |                    ^ not found in this scope
+
+error: aborting due to previous error; 1 warning emitted
+";
+
+    assert_eq!(
+      parse_compiler_diagnostics(output, 73),
+      vec![
+        Diagnostic {
+          file: "main.rs".into(),
+          line: 3,
+          column: Some(9),
+          severity: Severity::Warning,
+          message: "unused variable: `y`".into(),
+        },
+        Diagnostic {
+          file: "main.rs".into(),
+          line: 2,
+          column: Some(20),
+          severity: Severity::Error,
+          message: "cannot find value `x` in this scope".into(),
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn unrecognized_output() {
+    assert!(parse_compiler_diagnostics("Segmentation fault", 50).is_empty());
+  }
+}
@@ -1,3 +1,5 @@
+use super::*;
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
   #[error("Failed to make request")]
@@ -8,4 +10,116 @@ pub enum Error {
   HeaderName(String),
   #[error("Invalid header value: {0}")]
   HeaderValue(String),
+  #[error("No base URL was set for the client")]
+  MissingBaseUrl,
+  #[error("Missing required authentication header: {0}")]
+  MissingAuth(String),
+  #[error(
+    "Waiting for submissions is not allowed on this instance, poll for the result instead"
+  )]
+  WaitNotAllowed,
+  #[error("Submission was rejected: {0}")]
+  Rejected(Value),
+  #[error("The submission queue is full, try again later")]
+  QueueFull,
+  #[error("Timed out waiting for submissions to finish")]
+  Timeout,
+  #[error("Server responded with status {status}: {body}")]
+  Api { status: u16, body: String },
+  #[error("I/O error")]
+  Io(#[from] std::io::Error),
+  #[error("Invalid callback request: {0}")]
+  Callback(String),
+  #[error(
+    "Submission input of {size} bytes exceeded the limit of {limit} bytes"
+  )]
+  InputTooLarge { size: usize, limit: usize },
+  #[error("Response body exceeded the limit of {limit} bytes")]
+  ResponseTooLarge { limit: usize },
+  #[error("Failed to deserialize response from {endpoint}: {snippet}")]
+  Deserialize {
+    endpoint: String,
+    snippet: String,
+    source: serde_json::Error,
+  },
+  #[error("stdin is not valid UTF-8 and can only be sent base64 encoded")]
+  BinaryStdin,
+  #[error("Field was not requested when fetching the submission: {0}")]
+  FieldNotFetched(String),
+  #[error("Preflight checks failed: {}", .0.join("; "))]
+  Preflight(Vec<String>),
+  #[error("Invalid root certificate: {0}")]
+  Certificate(String),
+  #[error("Failed to decode base64 encoded field: {0}")]
+  Base64(String),
+  #[cfg(feature = "zip")]
+  #[error("Failed to read or write zip archive")]
+  Zip(#[from] zip::result::ZipError),
+  #[cfg(feature = "zip")]
+  #[error("Archive entry escapes the destination directory: {0}")]
+  UnsafeArchivePath(String),
+}
+
+impl Error {
+  /// Whether the error was caused by a request or polling timeout.
+  pub fn is_timeout(&self) -> bool {
+    match self {
+      Self::Timeout => true,
+      Self::Request(error) => error.is_timeout(),
+      _ => false,
+    }
+  }
+
+  /// Whether the server rejected the request due to rate limiting.
+  pub fn is_rate_limited(&self) -> bool {
+    self.status_code() == Some(StatusCode::TOO_MANY_REQUESTS.as_u16())
+  }
+
+  /// Whether the server rejected the request due to invalid fields.
+  pub fn is_validation(&self) -> bool {
+    self.status_code() == Some(StatusCode::UNPROCESSABLE_ENTITY.as_u16())
+  }
+
+  /// The HTTP status code of the response that caused the error, if any.
+  pub fn status_code(&self) -> Option<u16> {
+    match self {
+      Self::Api { status, .. } => Some(*status),
+      Self::Request(error) => error.status().map(|status| status.as_u16()),
+      _ => None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn api(status: u16) -> Error {
+    Error::Api {
+      status,
+      body: String::new(),
+    }
+  }
+
+  #[test]
+  fn timeout() {
+    assert!(Error::Timeout.is_timeout());
+    assert!(!api(408).is_timeout());
+    assert_eq!(Error::Timeout.status_code(), None);
+  }
+
+  #[test]
+  fn rate_limited() {
+    assert!(api(429).is_rate_limited());
+    assert!(!api(422).is_rate_limited());
+    assert_eq!(api(429).status_code(), Some(429));
+  }
+
+  #[test]
+  fn validation() {
+    assert!(api(422).is_validation());
+    assert!(!api(429).is_validation());
+    assert!(!Error::WaitNotAllowed.is_validation());
+    assert_eq!(api(422).status_code(), Some(422));
+  }
 }
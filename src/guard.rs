@@ -0,0 +1,269 @@
+use super::*;
+
+/// A submission that is deleted when the guard is dropped, returned by
+/// `Client::create_submission_scoped`.
+///
+/// Since `Drop` can't be async, the delete request is spawned onto the
+/// current tokio runtime and may complete after the guard is gone, giving up
+/// after `Config::request_deadline` when set. Nothing is deleted if the guard
+/// is dropped outside of a runtime, or if the instance didn't return a token.
+#[derive(Debug)]
+pub struct SubmissionGuard {
+  token: String,
+  result: CreateResponse,
+  delete: Option<reqwest::RequestBuilder>,
+  deadline: Option<Duration>,
+}
+
+impl SubmissionGuard {
+  /// The token of the guarded submission.
+  pub fn token(&self) -> &str {
+    &self.token
+  }
+
+  /// The response to creating the submission.
+  pub fn result(&self) -> &CreateResponse {
+    &self.result
+  }
+}
+
+impl Drop for SubmissionGuard {
+  fn drop(&mut self) {
+    let Some(delete) = self.delete.take() else {
+      return;
+    };
+
+    let deadline = self.deadline;
+
+    if let Ok(handle) = tokio::runtime::Handle::try_current() {
+      handle.spawn(async move {
+        match deadline {
+          Some(deadline) => {
+            let _ = time::timeout(deadline, delete.send()).await;
+          }
+          None => {
+            let _ = delete.send().await;
+          }
+        }
+      });
+    }
+  }
+}
+
+impl Client {
+  /// Create a submission that is deleted once the returned guard is dropped,
+  /// which keeps ephemeral submissions, e.g. from test suites, from piling
+  /// up. Deleting submissions requires an authorization token and must be
+  /// enabled on the instance.
+  ///
+  /// The submission is always created, bypassing `Config::dedup_cache_size`,
+  /// so that a guard never deletes a submission shared with another caller.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config, Submission};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config {
+  ///   authorization_token: Some("token".into()),
+  ///   ..Default::default()
+  /// }).unwrap();
+  ///
+  /// let guard = client
+  ///   .create_submission_scoped(Submission {
+  ///     source_code: "print(Hello, world)".into(),
+  ///     language_id: 1,
+  ///     ..Submission::default()
+  ///   })
+  ///   .await
+  ///   .unwrap();
+  ///
+  /// let submission = client.get_submission(guard.token(), None).await.unwrap();
+  /// ```
+  pub async fn create_submission_scoped(
+    &self,
+    submission: Submission,
+  ) -> Result<SubmissionGuard> {
+    let result = self.create(submission, false).await?;
+
+    let token = result.token().to_owned();
+
+    Ok(SubmissionGuard {
+      delete: if token.is_empty() {
+        None
+      } else {
+        Some(self.prepare(&format!("/submissions/{token}"), Method::DELETE)?)
+      },
+      deadline: self.config.request_deadline,
+      token,
+      result,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, mockito::Server};
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn delete_on_drop() {
+    let mut server = Server::new_async().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        authorization_token: Some("token".into()),
+        ..Default::default()
+      })
+      .unwrap();
+
+    let create = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "token": "a" }"#)
+      .create();
+
+    let delete = server
+      .mock("DELETE", "/submissions/a")
+      .match_header("X-Auth-User", "token")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "token": "a" }"#)
+      .create();
+
+    let guard = client
+      .create_submission_scoped(Submission {
+        source_code: r#"print("Hello, world!")"#.into(),
+        language_id: 71,
+        ..Default::default()
+      })
+      .await
+      .unwrap();
+
+    assert_eq!(guard.token(), "a");
+    assert!(!delete.matched());
+
+    drop(guard);
+
+    time::timeout(Duration::from_secs(5), async {
+      while !delete.matched() {
+        sleep(Duration::from_millis(10)).await;
+      }
+    })
+    .await
+    .unwrap();
+
+    create.assert();
+    delete.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn bypass_dedup_cache() {
+    let mut server = Server::new_async().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        authorization_token: Some("token".into()),
+        dedup_cache_size: 8,
+        ..Default::default()
+      })
+      .unwrap();
+
+    let shared = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "token": "a" }"#)
+      .expect(1)
+      .create();
+
+    let scoped = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "token": "b" }"#)
+      .expect(1)
+      .create();
+
+    let delete_shared =
+      server.mock("DELETE", "/submissions/a").expect(0).create();
+
+    let delete_scoped = server
+      .mock("DELETE", "/submissions/b")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "token": "b" }"#)
+      .create();
+
+    let submission = Submission {
+      source_code: r#"print("Hello, world!")"#.into(),
+      language_id: 71,
+      ..Default::default()
+    };
+
+    let result = client.create_submission(submission.clone()).await.unwrap();
+
+    assert_eq!(result.token(), "a");
+
+    let guard = client.create_submission_scoped(submission).await.unwrap();
+
+    assert_eq!(guard.token(), "b");
+
+    drop(guard);
+
+    time::timeout(Duration::from_secs(5), async {
+      while !delete_scoped.matched() {
+        sleep(Duration::from_millis(10)).await;
+      }
+    })
+    .await
+    .unwrap();
+
+    shared.assert();
+    scoped.assert();
+    delete_shared.assert();
+    delete_scoped.assert();
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn no_delete_without_token() {
+    let mut server = Server::new_async().await;
+
+    let client = Client::new(&server.url())
+      .configure(Config {
+        wait: true,
+        ..Default::default()
+      })
+      .unwrap();
+
+    let create = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=true")
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{ "token": "", "status": { "id": 3, "description": "Accepted" } }"#,
+      )
+      .create();
+
+    let delete = server
+      .mock("DELETE", mockito::Matcher::Any)
+      .expect(0)
+      .create();
+
+    let guard = client
+      .create_submission_scoped(Submission {
+        source_code: r#"print("Hello, world!")"#.into(),
+        language_id: 71,
+        ..Default::default()
+      })
+      .await
+      .unwrap();
+
+    assert_eq!(guard.token(), "");
+
+    drop(guard);
+
+    sleep(Duration::from_millis(100)).await;
+
+    create.assert();
+    delete.assert();
+  }
+}
@@ -0,0 +1,521 @@
+use super::*;
+
+/// The judge0 API operations, implemented by `Client`.
+///
+/// Code that only needs these operations can be generic over this trait, so
+/// its tests can supply a mock implementation instead of an HTTP server.
+///
+/// The trait covers the operations that map onto judge0 endpoints, along with
+/// waiting and grading. Conveniences composed from them, such as
+/// `Client::language_map`, `Client::run_batch` or
+/// `Client::create_submission_scoped`, and raw or transport-specific requests
+/// remain inherent methods of `Client`.
+///
+/// ```rust
+/// use judge0_rs::{Error, Judge0Client, Submission};
+///
+/// async fn run(
+///   client: &impl Judge0Client,
+///   source_code: &str,
+/// ) -> Result<Submission, Error> {
+///   let result = client
+///     .create_submission(Submission {
+///       source_code: source_code.into(),
+///       language_id: 71,
+///       ..Submission::default()
+///     })
+///     .await?;
+///
+///   client.get_submission(result.token(), None).await
+/// }
+/// ```
+pub trait Judge0Client {
+  /// See `Client::authenticate`.
+  fn authenticate(&self) -> impl Future<Output = Result<bool>> + Send;
+
+  /// See `Client::authorize`.
+  fn authorize(&self) -> impl Future<Output = Result<bool>> + Send;
+
+  /// See `Client::get_languages`.
+  fn get_languages(&self)
+    -> impl Future<Output = Result<Vec<Language>>> + Send;
+
+  /// See `Client::get_language`.
+  fn get_language(
+    &self,
+    id: usize,
+  ) -> impl Future<Output = Result<Language>> + Send;
+
+  /// See `Client::get_statuses`.
+  fn get_statuses(&self) -> impl Future<Output = Result<Vec<Status>>> + Send;
+
+  /// See `Client::get_about`.
+  fn get_about(&self) -> impl Future<Output = Result<About>> + Send;
+
+  /// See `Client::get_config_info`.
+  fn get_config_info(&self) -> impl Future<Output = Result<ConfigInfo>> + Send;
+
+  /// See `Client::get_workers`.
+  fn get_workers(&self) -> impl Future<Output = Result<Vec<Worker>>> + Send;
+
+  /// See `Client::create_submission`.
+  fn create_submission(
+    &self,
+    submission: Submission,
+  ) -> impl Future<Output = Result<CreateResponse>> + Send;
+
+  /// See `Client::create_submission_fire_and_forget`.
+  fn create_submission_fire_and_forget(
+    &self,
+    submission: Submission,
+  ) -> impl Future<Output = Result<Token>> + Send;
+
+  /// See `Client::get_submission`.
+  fn get_submission(
+    &self,
+    token: &str,
+    fields: Option<&[SubmissionField]>,
+  ) -> impl Future<Output = Result<Submission>> + Send;
+
+  /// See `Client::delete_submission`.
+  fn delete_submission(
+    &self,
+    token: &str,
+    fields: Option<&str>,
+  ) -> impl Future<Output = Result<Submission>> + Send;
+
+  /// See `Client::list_submissions`.
+  fn list_submissions(
+    &self,
+    page: usize,
+    per_page: usize,
+    status_id: Option<usize>,
+  ) -> impl Future<Output = Result<SubmissionPage>> + Send;
+
+  /// See `Client::list_all_submissions`.
+  fn list_all_submissions(
+    &self,
+  ) -> impl Future<Output = Result<Vec<Submission>>> + Send;
+
+  /// See `Client::batch_submit`.
+  fn batch_submit(
+    &self,
+    submissions: Vec<Submission>,
+  ) -> impl Future<Output = Result<BatchSubmissionResponse>> + Send;
+
+  /// See `Client::get_batch_submission`.
+  fn get_batch_submission(
+    &self,
+    tokens: Vec<&str>,
+    fields: Option<&[SubmissionField]>,
+  ) -> impl Future<Output = Result<Vec<Submission>>> + Send;
+
+  /// See `Client::wait_for_submission`.
+  fn wait_for_submission(
+    &self,
+    token: &str,
+    interval: Duration,
+    timeout: Duration,
+  ) -> impl Future<Output = Result<Submission>> + Send;
+
+  /// See `Client::wait_until_status`.
+  fn wait_until_status(
+    &self,
+    token: &str,
+    target: usize,
+    interval: Duration,
+    timeout: Duration,
+  ) -> impl Future<Output = Result<Submission>> + Send;
+
+  /// See `Client::wait_for_batch`.
+  fn wait_for_batch(
+    &self,
+    tokens: &[&str],
+    interval: Duration,
+    timeout: Duration,
+  ) -> impl Future<Output = Result<Vec<Submission>>> + Send;
+
+  /// See `Client::grade`.
+  fn grade(
+    &self,
+    source_code: String,
+    language_id: usize,
+    cases: Vec<TestCase>,
+  ) -> impl Future<Output = Result<Vec<Submission>>> + Send;
+}
+
+impl Judge0Client for Client {
+  fn authenticate(&self) -> impl Future<Output = Result<bool>> + Send {
+    Client::authenticate(self)
+  }
+
+  fn authorize(&self) -> impl Future<Output = Result<bool>> + Send {
+    Client::authorize(self)
+  }
+
+  fn get_languages(
+    &self,
+  ) -> impl Future<Output = Result<Vec<Language>>> + Send {
+    Client::get_languages(self)
+  }
+
+  fn get_language(
+    &self,
+    id: usize,
+  ) -> impl Future<Output = Result<Language>> + Send {
+    Client::get_language(self, id)
+  }
+
+  fn get_statuses(&self) -> impl Future<Output = Result<Vec<Status>>> + Send {
+    Client::get_statuses(self)
+  }
+
+  fn get_about(&self) -> impl Future<Output = Result<About>> + Send {
+    Client::get_about(self)
+  }
+
+  fn get_config_info(&self) -> impl Future<Output = Result<ConfigInfo>> + Send {
+    Client::get_config_info(self)
+  }
+
+  fn get_workers(&self) -> impl Future<Output = Result<Vec<Worker>>> + Send {
+    Client::get_workers(self)
+  }
+
+  fn create_submission(
+    &self,
+    submission: Submission,
+  ) -> impl Future<Output = Result<CreateResponse>> + Send {
+    Client::create_submission(self, submission)
+  }
+
+  fn create_submission_fire_and_forget(
+    &self,
+    submission: Submission,
+  ) -> impl Future<Output = Result<Token>> + Send {
+    Client::create_submission_fire_and_forget(self, submission)
+  }
+
+  fn get_submission(
+    &self,
+    token: &str,
+    fields: Option<&[SubmissionField]>,
+  ) -> impl Future<Output = Result<Submission>> + Send {
+    Client::get_submission(self, token, fields)
+  }
+
+  fn delete_submission(
+    &self,
+    token: &str,
+    fields: Option<&str>,
+  ) -> impl Future<Output = Result<Submission>> + Send {
+    Client::delete_submission(self, token, fields)
+  }
+
+  fn list_submissions(
+    &self,
+    page: usize,
+    per_page: usize,
+    status_id: Option<usize>,
+  ) -> impl Future<Output = Result<SubmissionPage>> + Send {
+    Client::list_submissions(self, page, per_page, status_id)
+  }
+
+  fn list_all_submissions(
+    &self,
+  ) -> impl Future<Output = Result<Vec<Submission>>> + Send {
+    Client::list_all_submissions(self)
+  }
+
+  fn batch_submit(
+    &self,
+    submissions: Vec<Submission>,
+  ) -> impl Future<Output = Result<BatchSubmissionResponse>> + Send {
+    Client::batch_submit(self, submissions)
+  }
+
+  fn get_batch_submission(
+    &self,
+    tokens: Vec<&str>,
+    fields: Option<&[SubmissionField]>,
+  ) -> impl Future<Output = Result<Vec<Submission>>> + Send {
+    Client::get_batch_submission(self, tokens, fields)
+  }
+
+  fn wait_for_submission(
+    &self,
+    token: &str,
+    interval: Duration,
+    timeout: Duration,
+  ) -> impl Future<Output = Result<Submission>> + Send {
+    Client::wait_for_submission(self, token, interval, timeout)
+  }
+
+  fn wait_until_status(
+    &self,
+    token: &str,
+    target: usize,
+    interval: Duration,
+    timeout: Duration,
+  ) -> impl Future<Output = Result<Submission>> + Send {
+    Client::wait_until_status(self, token, target, interval, timeout)
+  }
+
+  fn wait_for_batch(
+    &self,
+    tokens: &[&str],
+    interval: Duration,
+    timeout: Duration,
+  ) -> impl Future<Output = Result<Vec<Submission>>> + Send {
+    Client::wait_for_batch(self, tokens, interval, timeout)
+  }
+
+  fn grade(
+    &self,
+    source_code: String,
+    language_id: usize,
+    cases: Vec<TestCase>,
+  ) -> impl Future<Output = Result<Vec<Submission>>> + Send {
+    Client::grade(self, source_code, language_id, cases)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, mockito::Server};
+
+  /// A canned implementation a downstream crate might use in its own tests.
+  struct MockClient {
+    stdout: String,
+  }
+
+  impl Judge0Client for MockClient {
+    async fn authenticate(&self) -> Result<bool> {
+      Ok(true)
+    }
+
+    async fn authorize(&self) -> Result<bool> {
+      Ok(true)
+    }
+
+    async fn get_languages(&self) -> Result<Vec<Language>> {
+      Ok(Vec::new())
+    }
+
+    async fn get_language(&self, _id: usize) -> Result<Language> {
+      Err(Error::Api {
+        status: 404,
+        body: String::new(),
+      })
+    }
+
+    async fn get_statuses(&self) -> Result<Vec<Status>> {
+      Ok(Vec::new())
+    }
+
+    async fn get_about(&self) -> Result<About> {
+      Ok(About::default())
+    }
+
+    async fn get_config_info(&self) -> Result<ConfigInfo> {
+      Ok(ConfigInfo::default())
+    }
+
+    async fn get_workers(&self) -> Result<Vec<Worker>> {
+      Ok(Vec::new())
+    }
+
+    async fn create_submission(
+      &self,
+      submission: Submission,
+    ) -> Result<CreateResponse> {
+      Ok(CreateResponse::Pending(
+        self.create_submission_fire_and_forget(submission).await?,
+      ))
+    }
+
+    async fn create_submission_fire_and_forget(
+      &self,
+      _submission: Submission,
+    ) -> Result<Token> {
+      Ok(Token { token: "a".into() })
+    }
+
+    async fn get_submission(
+      &self,
+      token: &str,
+      _fields: Option<&[SubmissionField]>,
+    ) -> Result<Submission> {
+      Ok(Submission {
+        token: Some(token.into()),
+        stdout: Some(self.stdout.clone()),
+        status: Some(Status {
+          id: 3,
+          description: "Accepted".into(),
+        }),
+        ..Default::default()
+      })
+    }
+
+    async fn delete_submission(
+      &self,
+      token: &str,
+      _fields: Option<&str>,
+    ) -> Result<Submission> {
+      self.get_submission(token, None).await
+    }
+
+    async fn list_submissions(
+      &self,
+      _page: usize,
+      _per_page: usize,
+      _status_id: Option<usize>,
+    ) -> Result<SubmissionPage> {
+      Ok(SubmissionPage::default())
+    }
+
+    async fn list_all_submissions(&self) -> Result<Vec<Submission>> {
+      Ok(Vec::new())
+    }
+
+    async fn batch_submit(
+      &self,
+      submissions: Vec<Submission>,
+    ) -> Result<BatchSubmissionResponse> {
+      Ok(BatchSubmissionResponse(
+        submissions
+          .iter()
+          .map(|_| BatchToken::Token(Token { token: "a".into() }))
+          .collect(),
+      ))
+    }
+
+    async fn get_batch_submission(
+      &self,
+      tokens: Vec<&str>,
+      fields: Option<&[SubmissionField]>,
+    ) -> Result<Vec<Submission>> {
+      let mut submissions = Vec::new();
+
+      for token in tokens {
+        submissions.push(self.get_submission(token, fields).await?);
+      }
+
+      Ok(submissions)
+    }
+
+    async fn wait_for_submission(
+      &self,
+      token: &str,
+      _interval: Duration,
+      _timeout: Duration,
+    ) -> Result<Submission> {
+      self.get_submission(token, None).await
+    }
+
+    async fn wait_until_status(
+      &self,
+      token: &str,
+      _target: usize,
+      _interval: Duration,
+      _timeout: Duration,
+    ) -> Result<Submission> {
+      self.get_submission(token, None).await
+    }
+
+    async fn wait_for_batch(
+      &self,
+      tokens: &[&str],
+      _interval: Duration,
+      _timeout: Duration,
+    ) -> Result<Vec<Submission>> {
+      self.get_batch_submission(tokens.to_vec(), None).await
+    }
+
+    async fn grade(
+      &self,
+      _source_code: String,
+      _language_id: usize,
+      cases: Vec<TestCase>,
+    ) -> Result<Vec<Submission>> {
+      let tokens = vec!["a"; cases.len()];
+
+      self.get_batch_submission(tokens, None).await
+    }
+  }
+
+  async fn run(client: &impl Judge0Client) -> Result<Option<String>> {
+    let result = client
+      .create_submission(Submission {
+        source_code: r#"print("hello")"#.into(),
+        language_id: 71,
+        ..Default::default()
+      })
+      .await?;
+
+    let token = result.token();
+
+    Ok(
+      client
+        .wait_for_submission(token, Duration::ZERO, Duration::from_secs(1))
+        .await?
+        .stdout,
+    )
+  }
+
+  #[tokio::test]
+  async fn mock_client() {
+    let client = MockClient {
+      stdout: "hello\n".into(),
+    };
+
+    assert_eq!(run(&client).await.unwrap().as_deref(), Some("hello\n"));
+  }
+
+  #[tokio::test]
+  async fn mock_client_grade() {
+    let client = MockClient {
+      stdout: "hello\n".into(),
+    };
+
+    let results = client
+      .grade(
+        r#"print("hello")"#.into(),
+        71,
+        vec![TestCase::default(), TestCase::default()],
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(Submission::is_finished));
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn client() {
+    let mut server = Server::new_async().await;
+
+    let create = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "token": "a" }"#)
+      .create();
+
+    let poll = server
+      .mock("GET", "/submissions/a?base64_encoded=false&wait=false&fields=*")
+      .with_status(200)
+      .with_header("content-type", "application/json")
+      .with_body(
+        r#"{ "stdout": "hello\n", "status": { "id": 3, "description": "Accepted" } }"#,
+      )
+      .create();
+
+    let client = Client::new(&server.url());
+
+    assert_eq!(run(&client).await.unwrap().as_deref(), Some("hello\n"));
+
+    create.assert();
+    poll.assert();
+  }
+}
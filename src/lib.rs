@@ -1,17 +1,73 @@
 use {
+  base64::{
+    engine::general_purpose::{GeneralPurpose, STANDARD, URL_SAFE},
+    write::EncoderStringWriter,
+    Engine,
+  },
   chrono::prelude::*,
-  http::{HeaderMap, HeaderName, HeaderValue, Method},
+  flate2::{read::GzDecoder, write::GzEncoder, Compression},
+  futures_util::future::join_all,
+  http::{
+    header::{ACCEPT, AUTHORIZATION, CONTENT_ENCODING, CONTENT_TYPE},
+    HeaderMap, HeaderName, HeaderValue, Method, StatusCode,
+  },
+  rand::Rng,
+  reqwest::{multipart::Form, redirect},
   serde::de::DeserializeOwned,
-  serde::{Deserialize, Serialize},
+  serde::{Deserialize, Deserializer, Serialize},
   serde_json::Value,
-  std::str::FromStr,
+  std::{
+    collections::{HashMap, VecDeque},
+    fmt::{self, Display, Formatter},
+    fs,
+    future::Future,
+    hash::{DefaultHasher, Hash, Hasher},
+    io::{self, Read, Write},
+    ops::Range,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+  },
+  tokio::{
+    sync::mpsc,
+    task::JoinHandle,
+    time::{self, sleep},
+  },
 };
 
+#[cfg(feature = "zip")]
+mod additional_files;
+mod builder;
+#[cfg(feature = "callback")]
+mod callback;
 mod client;
 mod config;
+mod diagnostic;
 mod error;
+mod guard;
+mod judge0_client;
 mod model;
+#[cfg(feature = "stream")]
+mod stream;
+
+use client::{http_client_builder, truncate};
 
 type Result<T = (), E = Error> = std::result::Result<T, E>;
 
-pub use {client::Client, config::Config, error::Error, model::*};
+pub use {
+  builder::ClientBuilder,
+  client::Client,
+  config::Config,
+  diagnostic::{parse_compiler_diagnostics, Diagnostic, Severity},
+  error::Error,
+  guard::SubmissionGuard,
+  judge0_client::Judge0Client,
+  model::*,
+};
+
+#[cfg(feature = "callback")]
+pub use callback::{
+  parse_callback, verify_callback_secret, verify_hmac, BatchCallbackCollector,
+  CallbackAck,
+};
@@ -1,5 +1,27 @@
 use super::*;
 
+const MAX_REPORT_OUTPUT_BYTES: usize = 1024;
+
+/// Fields describing a submission's result or delivery rather than its
+/// input, left out of shareable payloads.
+const NON_INPUT_FIELDS: &[&str] = &[
+  "callback_url",
+  "compile_output",
+  "created_at",
+  "exit_code",
+  "exit_signal",
+  "finished_at",
+  "memory",
+  "message",
+  "queue",
+  "status",
+  "stderr",
+  "stdout",
+  "time",
+  "token",
+  "wall_time",
+];
+
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct Language {
   pub id: usize,
@@ -10,12 +32,105 @@ pub struct Language {
   pub run_cmd: Option<String>,
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+impl Language {
+  /// The version in parentheses at the end of the name, e.g. `3.8.1` for
+  /// "Python (3.8.1)".
+  pub fn version(&self) -> Option<String> {
+    let (_, rest) = self.name.split_once('(')?;
+
+    rest
+      .rsplit_once(')')
+      .map(|(version, _)| version.trim().to_owned())
+  }
+
+  /// The name without its version, e.g. `Python` for "Python (3.8.1)".
+  pub fn base_name(&self) -> &str {
+    self
+      .name
+      .split_once('(')
+      .map_or(self.name.as_str(), |(name, _)| name)
+      .trim()
+  }
+
+  /// Whether the language is archived. Listings of active languages leave
+  /// `is_archived` out, so a missing value counts as not archived.
+  pub fn is_archived(&self) -> bool {
+    self.is_archived.unwrap_or(false)
+  }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct Status {
   pub id: usize,
   pub description: String,
 }
 
+impl Status {
+  /// Classify the status as queued, successfully finished, or failed.
+  pub fn category(&self) -> StatusCategory {
+    match self.id {
+      1 | 2 => StatusCategory::Queued,
+      3 => StatusCategory::Success,
+      _ => StatusCategory::Failure,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatusCategory {
+  /// The submission is in queue or processing.
+  Queued,
+  /// The submission was accepted.
+  Success,
+  /// The submission finished with any other status.
+  Failure,
+}
+
+/// Signal that terminated a program, decoded from `exit_signal`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Signal {
+  /// SIGABRT, e.g. from a failed assertion or uncaught C++ exception.
+  Abort,
+  /// SIGBUS, an invalid memory access.
+  Bus,
+  /// SIGFPE, e.g. from an integer division by zero.
+  FloatingPoint,
+  /// SIGKILL, usually sent by the sandbox when a limit was exceeded.
+  Killed,
+  /// SIGSEGV, a segmentation fault.
+  Segv,
+  /// SIGPIPE, a write to a closed pipe.
+  Pipe,
+  /// SIGALRM, a timer expired.
+  Alarm,
+  /// SIGTERM, a request to terminate.
+  Terminated,
+  /// SIGXCPU, the CPU time limit was exceeded.
+  CpuLimit,
+  /// SIGXFSZ, the file size limit was exceeded.
+  FileSizeLimit,
+  /// Any other signal number.
+  Other(i64),
+}
+
+impl From<i64> for Signal {
+  fn from(number: i64) -> Self {
+    match number {
+      6 => Self::Abort,
+      7 => Self::Bus,
+      8 => Self::FloatingPoint,
+      9 => Self::Killed,
+      11 => Self::Segv,
+      13 => Self::Pipe,
+      14 => Self::Alarm,
+      15 => Self::Terminated,
+      24 => Self::CpuLimit,
+      25 => Self::FileSizeLimit,
+      number => Self::Other(number),
+    }
+  }
+}
+
 #[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
 pub struct About {
   pub version: String,
@@ -24,6 +139,135 @@ pub struct About {
   pub maintainer: String,
 }
 
+/// The judge0 versions this crate is known to work with, as `(major,
+/// minor, patch)`.
+pub const SUPPORTED_VERSIONS: Range<(u64, u64, u64)> = (1, 0, 0)..(2, 0, 0);
+
+/// Whether an instance's version is in `SUPPORTED_VERSIONS`, returned by
+/// `Client::check_compatibility`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Compatibility {
+  /// The version reported by the instance.
+  pub server_version: String,
+  /// Whether the version is supported. False if it can't be parsed.
+  pub compatible: bool,
+}
+
+impl Compatibility {
+  pub(crate) fn new(server_version: &str) -> Self {
+    Self {
+      server_version: server_version.to_owned(),
+      compatible: parse_version(server_version)
+        .is_some_and(|version| SUPPORTED_VERSIONS.contains(&version)),
+    }
+  }
+}
+
+/// Parse a version like `1.13.1` or `v1.13.1-extra`, where missing minor and
+/// patch numbers count as zero.
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+  let version = version.trim();
+
+  let mut numbers = version
+    .strip_prefix('v')
+    .unwrap_or(version)
+    .split(['-', '+'])
+    .next()?
+    .split('.')
+    .map(str::parse::<u64>);
+
+  Some((
+    numbers.next()?.ok()?,
+    numbers.next().transpose().ok()?.unwrap_or_default(),
+    numbers.next().transpose().ok()?.unwrap_or_default(),
+  ))
+}
+
+/// The configuration of a judge0 instance, including the limits submissions
+/// must stay within.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ConfigInfo {
+  pub maintenance_mode: bool,
+  pub enable_wait_result: bool,
+  pub enable_compiler_options: bool,
+  pub allowed_languages_for_compile_options: Vec<String>,
+  pub enable_command_line_arguments: bool,
+  pub enable_submission_delete: bool,
+  pub enable_callbacks: bool,
+  pub enable_additional_files: bool,
+  pub max_queue_size: usize,
+  pub cpu_time_limit: f64,
+  pub max_cpu_time_limit: f64,
+  pub cpu_extra_time: f64,
+  pub max_cpu_extra_time: f64,
+  pub wall_time_limit: f64,
+  pub max_wall_time_limit: f64,
+  pub memory_limit: f64,
+  pub max_memory_limit: f64,
+  pub stack_limit: usize,
+  pub max_stack_limit: usize,
+  pub max_processes_and_or_threads: usize,
+  pub max_max_processes_and_or_threads: usize,
+  pub allow_enable_per_process_and_thread_time_limit: bool,
+  pub allow_enable_per_process_and_thread_memory_limit: bool,
+  pub max_file_size: usize,
+  pub max_max_file_size: usize,
+  pub number_of_runs: usize,
+  pub max_number_of_runs: usize,
+  pub enable_batched_submissions: bool,
+  pub max_submission_batch_size: usize,
+  pub allow_enable_network: bool,
+}
+
+/// The features and limits of a judge0 instance, derived from `ConfigInfo`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Capabilities {
+  /// Whether submissions may set `enable_network`.
+  pub allow_enable_network: bool,
+  /// Whether submissions may be created with `wait=true`.
+  pub enable_wait: bool,
+  /// Whether batch submissions are supported.
+  pub enable_batched_submissions: bool,
+  /// Whether submissions may set `callback_url`.
+  pub enable_callbacks: bool,
+  /// Whether submissions may set `additional_files`.
+  pub enable_additional_files: bool,
+  /// Whether submissions may set `compiler_options`.
+  pub enable_compiler_options: bool,
+  /// Whether submissions may set `command_line_arguments`.
+  pub enable_command_line_arguments: bool,
+  /// Whether submissions may be deleted.
+  pub enable_submission_delete: bool,
+  /// Maximum number of submissions in a batch.
+  pub max_submission_batch_size: usize,
+  /// Maximum `cpu_time_limit` in seconds.
+  pub max_cpu_time_limit: f64,
+  /// Maximum `wall_time_limit` in seconds.
+  pub max_wall_time_limit: f64,
+  /// Maximum `memory_limit` in kilobytes.
+  pub max_memory_limit: f64,
+}
+
+impl From<&ConfigInfo> for Capabilities {
+  fn from(config_info: &ConfigInfo) -> Self {
+    Self {
+      allow_enable_network: config_info.allow_enable_network,
+      enable_wait: config_info.enable_wait_result,
+      enable_batched_submissions: config_info.enable_batched_submissions,
+      enable_callbacks: config_info.enable_callbacks,
+      enable_additional_files: config_info.enable_additional_files,
+      enable_compiler_options: config_info.enable_compiler_options,
+      enable_command_line_arguments: config_info.enable_command_line_arguments,
+      enable_submission_delete: config_info.enable_submission_delete,
+      max_submission_batch_size: config_info.max_submission_batch_size,
+      max_cpu_time_limit: config_info.max_cpu_time_limit,
+      max_wall_time_limit: config_info.max_wall_time_limit,
+      max_memory_limit: config_info.max_memory_limit,
+    }
+  }
+}
+
 #[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
 pub struct Worker {
   pub queue: String,
@@ -35,12 +279,21 @@ pub struct Worker {
   pub failed: usize,
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+impl Worker {
+  /// Whether every available worker on this queue is paused.
+  pub fn is_paused(&self) -> bool {
+    self.available > 0 && self.paused >= self.available
+  }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Submission {
   /// Program’s source code.
+  #[serde(default)]
   pub source_code: String,
 
   /// The submission language identifier.
+  #[serde(default)]
   pub language_id: usize,
 
   /// Options for the compiler (i.e. compiler flags).
@@ -58,11 +311,13 @@ pub struct Submission {
 
   /// Default runtime limit for every program. Time in which the OS assigns
   /// the processor to different tasks is not counted.
+  #[serde(default, deserialize_with = "deserialize_decimal")]
   pub cpu_time_limit: Option<f64>,
 
   /// When a time limit is exceeded, wait for extra time, before killing the
   /// program. This has the advantage that the real execution time is
   /// reported, even though it slightly exceeds the limit.
+  #[serde(default, deserialize_with = "deserialize_decimal")]
   pub cpu_extra_time: Option<f64>,
 
   /// Limit wall-clock time in seconds. Decimal numbers are allowed. This clock
@@ -71,9 +326,11 @@ pub struct Submission {
   /// external event. We recommend to use cpu_time_limit as the main limit, but
   /// set wall_time_limit to a much higher value as a precaution against
   /// sleeping programs.
+  #[serde(default, deserialize_with = "deserialize_decimal")]
   pub wall_time_limit: Option<f64>,
 
   /// Limit address space of the program.
+  #[serde(default, deserialize_with = "deserialize_decimal")]
   pub memory_limit: Option<f64>,
 
   /// Limit process stack.
@@ -111,6 +368,11 @@ pub struct Submission {
   /// request body after submission has been done.
   pub callback_url: Option<String>,
 
+  /// Name of the worker queue the submission should be routed to. This is
+  /// not part of the standard judge0 API and requires instance support.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub queue: Option<String>,
+
   /// Standard output of the program after execution.
   pub stdout: Option<String>,
 
@@ -131,23 +393,2110 @@ pub struct Submission {
   pub exit_signal: Option<i64>,
 
   /// Submission status.
+  #[serde(default, deserialize_with = "deserialize_status")]
   pub status: Option<Status>,
 
   /// Date and time when submission was created.
+  #[serde(default, deserialize_with = "deserialize_timestamp")]
   pub created_at: Option<DateTime<Utc>>,
 
   /// Date and time when submission was processed.
+  #[serde(default, deserialize_with = "deserialize_timestamp")]
   pub finished_at: Option<DateTime<Utc>>,
 
   /// Unique submission token which can be used to get a specific submission.
   pub token: Option<String>,
 
   /// Program’s run time.
+  #[serde(default, deserialize_with = "deserialize_decimal")]
   pub time: Option<f64>,
 
   /// Program’s wall time. Will be greater or equal to time.
+  #[serde(default, deserialize_with = "deserialize_decimal")]
   pub wall_time: Option<f64>,
 
   /// Memory used by the program after execution.
+  #[serde(default, deserialize_with = "deserialize_decimal")]
   pub memory: Option<f64>,
+
+  /// Overrides `Config::wait` when creating this submission. Never sent to
+  /// the server.
+  #[serde(skip)]
+  pub wait_override: Option<bool>,
+
+  /// Overrides `Config::base64_encoded` when creating this submission. Never
+  /// sent to the server.
+  #[serde(skip)]
+  pub base64_encoded_override: Option<bool>,
+
+  /// Whether `stdin` already holds base64 encoded data, as set by
+  /// `Submission::set_stdin_from_reader`, so it isn't encoded again.
+  #[serde(skip)]
+  pub stdin_base64_encoded: bool,
+
+  /// Fields requested when the submission was fetched, or `None` if all
+  /// fields were requested.
+  #[serde(skip)]
+  pub requested_fields: Option<Vec<String>>,
+
+  /// Opaque client-side data for correlating the submission with your own
+  /// records, such as a job id. Never sent to the server.
+  #[serde(skip)]
+  pub metadata: HashMap<String, String>,
+
+  /// Fields returned by the server that this struct doesn't model, such as
+  /// those added in newer judge0 versions. These are sent back when the
+  /// submission is serialized.
+  #[serde(flatten)]
+  pub extra: HashMap<String, Value>,
+}
+
+impl Submission {
+  /// Whether the submission has finished, i.e. its status is neither In
+  /// Queue nor Processing.
+  pub fn is_finished(&self) -> bool {
+    self
+      .status
+      .as_ref()
+      .is_some_and(|status| status.category() != StatusCategory::Queued)
+  }
+
+  /// Whether the submission is In Queue.
+  pub fn is_queued(&self) -> bool {
+    self.status.as_ref().is_some_and(|status| status.id == 1)
+  }
+
+  /// Whether the submission is Processing.
+  pub fn is_processing(&self) -> bool {
+    self.status.as_ref().is_some_and(|status| status.id == 2)
+  }
+
+  /// Whether any input field contains characters judge0 may fail to convert
+  /// to UTF-8 unless the submission is base64 encoded.
+  pub(crate) fn needs_base64(&self) -> bool {
+    [
+      Some(&self.source_code),
+      self.stdin.as_ref(),
+      self.expected_output.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+    .any(|field| {
+      field.chars().any(|c| {
+        c == char::REPLACEMENT_CHARACTER
+          || (c.is_control() && !matches!(c, '\n' | '\r' | '\t'))
+      })
+    })
+  }
+
+  /// Hash of everything that goes into the request creating the submission,
+  /// i.e. every serialized field and the effective `wait` and
+  /// `base64_encoded` settings, used to skip identical submissions.
+  pub(crate) fn content_hash(&self, wait: bool, base64_encoded: bool) -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    (
+      serde_json::to_value(self)
+        .map(|value| value.to_string())
+        .unwrap_or_default(),
+      self.stdin_base64_encoded,
+      wait,
+      base64_encoded,
+    )
+      .hash(&mut hasher);
+
+    hasher.finish()
+  }
+
+  /// Check the submission against the limits and features of an instance,
+  /// returning a description of every violation. An empty list means the
+  /// instance should accept the submission.
+  pub fn validate(&self, config_info: &ConfigInfo) -> Vec<String> {
+    let mut violations = Vec::new();
+
+    let mut disabled = |set: bool, allowed: bool, field: &str| {
+      if set && !allowed {
+        violations.push(format!("{field} is not enabled on this instance"));
+      }
+    };
+
+    disabled(
+      self.compiler_options.is_some(),
+      config_info.enable_compiler_options,
+      "compiler_options",
+    );
+
+    disabled(
+      self.command_line_arguments.is_some(),
+      config_info.enable_command_line_arguments,
+      "command_line_arguments",
+    );
+
+    disabled(
+      self.callback_url.is_some(),
+      config_info.enable_callbacks,
+      "callback_url",
+    );
+
+    disabled(
+      self.additional_files.is_some(),
+      config_info.enable_additional_files,
+      "additional_files",
+    );
+
+    disabled(
+      self.enable_network == Some(true),
+      config_info.allow_enable_network,
+      "enable_network",
+    );
+
+    disabled(
+      self.enable_per_process_and_thread_time_limit == Some(true),
+      config_info.allow_enable_per_process_and_thread_time_limit,
+      "enable_per_process_and_thread_time_limit",
+    );
+
+    disabled(
+      self.enable_per_process_and_thread_memory_limit == Some(true),
+      config_info.allow_enable_per_process_and_thread_memory_limit,
+      "enable_per_process_and_thread_memory_limit",
+    );
+
+    let mut exceeds = |value: Option<f64>, max: f64, field: &str| {
+      if let Some(value) = value.filter(|value| *value > max) {
+        violations
+          .push(format!("{field} {value} exceeds the maximum of {max}"));
+      }
+    };
+
+    exceeds(
+      self.cpu_time_limit,
+      config_info.max_cpu_time_limit,
+      "cpu_time_limit",
+    );
+
+    exceeds(
+      self.cpu_extra_time,
+      config_info.max_cpu_extra_time,
+      "cpu_extra_time",
+    );
+
+    exceeds(
+      self.wall_time_limit,
+      config_info.max_wall_time_limit,
+      "wall_time_limit",
+    );
+
+    exceeds(
+      self.memory_limit,
+      config_info.max_memory_limit,
+      "memory_limit",
+    );
+
+    for (value, max, field) in [
+      (self.stack_limit, config_info.max_stack_limit, "stack_limit"),
+      (
+        self.max_processes_and_or_threads,
+        config_info.max_max_processes_and_or_threads,
+        "max_processes_and_or_threads",
+      ),
+      (
+        self.max_file_size,
+        config_info.max_max_file_size,
+        "max_file_size",
+      ),
+      (
+        self.number_of_runs,
+        config_info.max_number_of_runs,
+        "number_of_runs",
+      ),
+    ] {
+      exceeds(value.map(|value| value as f64), max as f64, field);
+    }
+
+    violations
+  }
+
+  /// Whether the given field was requested when the submission was fetched.
+  /// A field that was fetched but is `None` was returned as `null`.
+  pub fn was_fetched(&self, field: &str) -> bool {
+    self
+      .requested_fields
+      .as_ref()
+      .is_none_or(|fields| fields.iter().any(|name| name == field))
+  }
+
+  /// Serialize the submission to the exact JSON body `create_submission`
+  /// would send with the given configuration, which is handy for attaching
+  /// reproducers to bug reports.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Config, Submission};
+  ///
+  /// let submission = Submission {
+  ///   source_code: "print(Hello, world)".into(),
+  ///   language_id: 1,
+  ///   ..Submission::default()
+  /// };
+  ///
+  /// let json = submission.to_request_json(&Config::default()).unwrap();
+  /// ```
+  pub fn to_request_json(&self, config: &Config) -> Result<String> {
+    Ok(serde_json::to_string(
+      &self.request_body(
+        self
+          .base64_encoded_override
+          .unwrap_or(config.base64_encoded),
+        config.base64_engine(),
+      )?,
+    )?)
+  }
+
+  /// Serialize only the fields that differ from `template`, along with the
+  /// required `source_code` and `language_id`, to keep large batches of
+  /// similar submissions small.
+  ///
+  /// Omitted fields fall back to the instance's defaults rather than the
+  /// template's values, so the template should hold settings the instance
+  /// already applies.
+  ///
+  /// The body is for `base64_encoded=false`, so binary stdin set with
+  /// `set_stdin_from_reader` fails with `Error::BinaryStdin`.
+  ///
+  /// ```rust
+  /// use judge0_rs::Submission;
+  ///
+  /// let template = Submission {
+  ///   language_id: 71,
+  ///   ..Submission::default()
+  /// };
+  ///
+  /// let submission = Submission {
+  ///   source_code: "print(input())".into(),
+  ///   stdin: Some("hello".into()),
+  ///   ..template.clone()
+  /// };
+  ///
+  /// let json = submission.diff_serialize(&template).unwrap();
+  /// ```
+  pub fn diff_serialize(&self, template: &Submission) -> Result<String> {
+    let mut body = self.request_body(false, &STANDARD)?;
+
+    if let (Value::Object(fields), Value::Object(template)) =
+      (&mut body, template.request_body(false, &STANDARD)?)
+    {
+      fields.retain(|name, value| {
+        matches!(name.as_str(), "source_code" | "language_id")
+          || template.get(name) != Some(value)
+      });
+    }
+
+    Ok(serde_json::to_string(&body)?)
+  }
+
+  /// Encode the submission's input, i.e. its source, language, input and
+  /// limits, as a compressed, URL-safe base64 blob that can be shared, e.g.
+  /// in a link to a reproduction, and restored with `from_shareable`.
+  ///
+  /// ```rust
+  /// use judge0_rs::Submission;
+  ///
+  /// let submission = Submission {
+  ///   source_code: "print(input())".into(),
+  ///   language_id: 71,
+  ///   stdin: Some("hello".into()),
+  ///   ..Submission::default()
+  /// };
+  ///
+  /// let link = format!("https://example.com/share/{}", submission.to_shareable());
+  /// ```
+  pub fn to_shareable(&self) -> String {
+    let mut fields = match serde_json::to_value(self) {
+      Ok(Value::Object(fields)) => fields,
+      _ => serde_json::Map::new(),
+    };
+
+    fields.retain(|name, value| {
+      !value.is_null() && !NON_INPUT_FIELDS.contains(&name.as_str())
+    });
+
+    if self.stdin_base64_encoded {
+      fields.insert("stdin_base64_encoded".into(), Value::Bool(true));
+    }
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+
+    // Writing to and finishing an in-memory encoder can't fail.
+    let _ = serde_json::to_writer(&mut encoder, &fields);
+
+    URL_SAFE.encode(encoder.finish().unwrap_or_default())
+  }
+
+  /// Restore a submission's input from a blob created with `to_shareable`.
+  ///
+  /// ```rust
+  /// use judge0_rs::Submission;
+  ///
+  /// let submission = Submission::from_shareable(blob).unwrap();
+  /// ```
+  pub fn from_shareable(blob: &str) -> Result<Submission> {
+    let compressed = URL_SAFE
+      .decode(blob.trim())
+      .map_err(|_| Error::Base64("shareable".into()))?;
+
+    let mut json = Vec::new();
+
+    GzDecoder::new(compressed.as_slice()).read_to_end(&mut json)?;
+
+    let mut fields =
+      serde_json::from_slice::<serde_json::Map<String, Value>>(&json)?;
+
+    let stdin_base64_encoded = fields
+      .remove("stdin_base64_encoded")
+      .and_then(|value| value.as_bool())
+      .unwrap_or_default();
+
+    let mut submission =
+      serde_json::from_value::<Submission>(Value::Object(fields))?;
+
+    if stdin_base64_encoded {
+      submission.stdin_base64_encoded = true;
+      submission.base64_encoded_override = Some(true);
+    }
+
+    Ok(submission)
+  }
+
+  /// Build the request body, omitting unset fields and base64 encoding the
+  /// input fields with `engine` if enabled. Input set with
+  /// `set_stdin_from_reader` is held in standard base64, so it is re-encoded
+  /// when another engine is used.
+  pub(crate) fn request_body(
+    &self,
+    base64_encoded: bool,
+    engine: &GeneralPurpose,
+  ) -> Result<Value> {
+    let mut body = serde_json::to_value(self)?;
+
+    if let Value::Object(fields) = &mut body {
+      fields.retain(|_, value| !value.is_null());
+
+      let stdin = |value: &str| {
+        STANDARD
+          .decode(value)
+          .map_err(|_| Error::Base64("stdin".into()))
+      };
+
+      if base64_encoded {
+        for name in ["source_code", "stdin", "expected_output"] {
+          if let Some(Value::String(value)) = fields.get_mut(name) {
+            *value = if name == "stdin" && self.stdin_base64_encoded {
+              engine.encode(stdin(value)?)
+            } else {
+              engine.encode(&value)
+            };
+          }
+        }
+      } else if self.stdin_base64_encoded {
+        if let Some(Value::String(value)) = fields.get_mut("stdin") {
+          *value =
+            String::from_utf8(stdin(value)?).map_err(|_| Error::BinaryStdin)?;
+        }
+      }
+    }
+
+    Ok(body)
+  }
+
+  /// Decode the base64 encoded text fields of a submission fetched with
+  /// `base64_encoded=true`, including the input fields, so they can be
+  /// displayed as submitted. Judge0 wraps encoded values across lines, so
+  /// whitespace is ignored. Binary output that isn't valid UTF-8 is decoded
+  /// lossily instead of failing the whole response.
+  pub(crate) fn decode(&mut self, engine: &GeneralPurpose) -> Result {
+    let decode = |name: &str, value: &mut String| -> Result {
+      let encoded = value
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace())
+        .collect::<String>();
+
+      *value = String::from_utf8_lossy(
+        &engine
+          .decode(encoded)
+          .map_err(|_| Error::Base64(name.into()))?,
+      )
+      .into_owned();
+
+      Ok(())
+    };
+
+    decode("source_code", &mut self.source_code)?;
+
+    for (name, field) in [
+      ("stdin", &mut self.stdin),
+      ("expected_output", &mut self.expected_output),
+      ("stdout", &mut self.stdout),
+      ("stderr", &mut self.stderr),
+      ("compile_output", &mut self.compile_output),
+      ("message", &mut self.message),
+    ] {
+      if let Some(value) = field {
+        decode(name, value)?;
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Check that fields which are already base64 encoded, i.e. the input
+  /// fields of a submission prepared for `base64_encoded=true` by hand and
+  /// `additional_files`, decode cleanly, so mistakes fail with
+  /// `Error::Base64` before the request is sent. Input fields must use the
+  /// alphabet selected by `Config::base64_url_safe`, and `additional_files`
+  /// the standard one it is always encoded with. Whitespace is ignored.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Config, Submission};
+  ///
+  /// let submission = Submission {
+  ///   source_code: "cHJpbnQoImhlbGxvIik=".into(),
+  ///   language_id: 71,
+  ///   ..Submission::default()
+  /// };
+  ///
+  /// submission.validate_base64(&Config::default()).unwrap();
+  /// ```
+  pub fn validate_base64(&self, config: &Config) -> Result {
+    for (name, field, engine) in [
+      (
+        "source_code",
+        Some(&self.source_code),
+        config.base64_engine(),
+      ),
+      ("stdin", self.stdin.as_ref(), config.base64_engine()),
+      (
+        "expected_output",
+        self.expected_output.as_ref(),
+        config.base64_engine(),
+      ),
+      (
+        "additional_files",
+        self.additional_files.as_ref(),
+        &STANDARD,
+      ),
+    ] {
+      let Some(value) = field else {
+        continue;
+      };
+
+      let encoded = value
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace())
+        .collect::<String>();
+
+      if engine.decode(encoded).is_err() {
+        return Err(Error::Base64(name.into()));
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Program’s run time as a `Duration`.
+  pub fn cpu_time(&self) -> Option<Duration> {
+    self
+      .time
+      .and_then(|time| Duration::try_from_secs_f64(time).ok())
+  }
+
+  /// Program’s wall time as a `Duration`.
+  pub fn wall_clock_time(&self) -> Option<Duration> {
+    self
+      .wall_time
+      .and_then(|wall_time| Duration::try_from_secs_f64(wall_time).ok())
+  }
+
+  /// Set `command_line_arguments` from individual arguments, quoting them so
+  /// the shell judge0 runs the program with splits them back unchanged.
+  ///
+  /// ```rust
+  /// use judge0_rs::Submission;
+  ///
+  /// let mut submission = Submission::default();
+  ///
+  /// submission.set_args(&["--name", "Judge0 user"]);
+  ///
+  /// assert_eq!(
+  ///   submission.command_line_arguments.as_deref(),
+  ///   Some("--name 'Judge0 user'")
+  /// );
+  /// ```
+  pub fn set_args(&mut self, args: &[&str]) {
+    self.command_line_arguments = Some(
+      args
+        .iter()
+        .map(|arg| quote_arg(arg))
+        .collect::<Vec<String>>()
+        .join(" "),
+    );
+  }
+
+  /// Set `wall_time_limit` to `cpu_time_limit` times `multiplier`, so that a
+  /// program sleeping or blocked on I/O is still stopped eventually. Does
+  /// nothing if `cpu_time_limit` is unset.
+  ///
+  /// ```rust
+  /// use judge0_rs::Submission;
+  ///
+  /// let mut submission = Submission {
+  ///   cpu_time_limit: Some(2.0),
+  ///   ..Submission::default()
+  /// };
+  ///
+  /// submission.auto_wall_time(3.0);
+  ///
+  /// assert_eq!(submission.wall_time_limit, Some(6.0));
+  /// ```
+  pub fn auto_wall_time(&mut self, multiplier: f64) {
+    if let Some(cpu_time_limit) = self.cpu_time_limit {
+      self.wall_time_limit = Some(cpu_time_limit * multiplier);
+    }
+  }
+
+  /// Apply conservative limits for running untrusted code: files written by
+  /// the program are capped at 1024 KB, it may create at most 16 processes
+  /// or threads, network access is disabled, and the time and memory limits
+  /// apply to each process and thread.
+  ///
+  /// Instances may disallow per-process limits, which `validate` reports.
+  ///
+  /// ```rust
+  /// use judge0_rs::Submission;
+  ///
+  /// let mut submission = Submission {
+  ///   source_code: "print(input())".into(),
+  ///   language_id: 71,
+  ///   ..Submission::default()
+  /// };
+  ///
+  /// submission.harden();
+  /// ```
+  pub fn harden(&mut self) {
+    self.max_file_size = Some(1024);
+    self.max_processes_and_or_threads = Some(16);
+    self.enable_network = Some(false);
+    self.enable_per_process_and_thread_time_limit = Some(true);
+    self.enable_per_process_and_thread_memory_limit = Some(true);
+  }
+
+  /// Read stdin from `reader`, base64 encoding it as it is read so binary
+  /// input never has to be held as a `String`. This forces base64 encoding
+  /// when the submission is created.
+  ///
+  /// ```rust
+  /// use {judge0_rs::Submission, std::fs::File};
+  ///
+  /// let mut submission = Submission {
+  ///   source_code: "print(input())".into(),
+  ///   language_id: 71,
+  ///   ..Submission::default()
+  /// };
+  ///
+  /// submission
+  ///   .set_stdin_from_reader(File::open("input.bin").unwrap())
+  ///   .unwrap();
+  /// ```
+  pub fn set_stdin_from_reader(&mut self, mut reader: impl Read) -> Result {
+    let mut writer = EncoderStringWriter::new(&STANDARD);
+
+    io::copy(&mut reader, &mut writer)?;
+
+    self.stdin = Some(writer.into_inner());
+    self.stdin_base64_encoded = true;
+    self.base64_encoded_override = Some(true);
+
+    Ok(())
+  }
+
+  /// Point `callback_url` at `url` with `secret` appended as a `secret` query
+  /// parameter, so a callback handler can reject requests that don't carry
+  /// it. See `verify_callback_secret`.
+  ///
+  /// ```rust
+  /// use judge0_rs::Submission;
+  ///
+  /// let mut submission = Submission::default();
+  ///
+  /// submission.set_callback_with_secret("https://example.com/callback", "s3cret");
+  ///
+  /// assert_eq!(
+  ///   submission.callback_url.as_deref(),
+  ///   Some("https://example.com/callback?secret=s3cret")
+  /// );
+  /// ```
+  pub fn set_callback_with_secret(&mut self, url: &str, secret: &str) {
+    let separator = if url.contains('?') { '&' } else { '?' };
+
+    self.callback_url = Some(format!(
+      "{url}{separator}secret={}",
+      encode_query_component(secret)
+    ));
+  }
+
+  /// Whether the memory used by the program exceeded `limit`, in the same
+  /// unit as `memory`. `None` if memory usage wasn't reported.
+  pub fn memory_exceeded(&self, limit: f64) -> Option<bool> {
+    self.memory.map(|memory| memory > limit)
+  }
+
+  /// Whether the program’s run time exceeded `limit` seconds. `None` if the
+  /// run time wasn't reported.
+  pub fn time_exceeded(&self, limit: f64) -> Option<bool> {
+    self.time.map(|time| time > limit)
+  }
+
+  /// The lines of the program's standard output, split lazily without
+  /// their line endings. Submissions fetched with `base64_encoded=true` are
+  /// decoded by the client, so this always yields plain text.
+  pub fn stdout_lines(&self) -> Option<impl Iterator<Item = &str>> {
+    self.stdout.as_deref().map(str::lines)
+  }
+
+  /// Render a multi-line, human-readable summary for command line output:
+  /// status, time, memory, and any compile output, stdout and stderr, each
+  /// truncated to a kilobyte. Submissions fetched with
+  /// `base64_encoded=true` are decoded by the client, so output is shown as
+  /// plain text.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let submission = client.get_submission("a", None).await.unwrap();
+  ///
+  /// println!("{}", submission.report());
+  /// ```
+  pub fn report(&self) -> String {
+    let mut lines = vec![format!(
+      "Status: {}",
+      self
+        .status
+        .as_ref()
+        .map_or("Unknown".into(), |status| format!(
+          "{} ({})",
+          status.description, status.id
+        ))
+    )];
+
+    if let Some(time) = self.time {
+      lines.push(format!("Time: {time} s"));
+    }
+
+    if let Some(memory) = self.memory {
+      lines.push(format!("Memory: {memory} KB"));
+    }
+
+    if let Some(exit_code) = self.exit_code {
+      lines.push(format!("Exit code: {exit_code}"));
+    }
+
+    for (name, output) in [
+      ("Compile output", &self.compile_output),
+      ("Stdout", &self.stdout),
+      ("Stderr", &self.stderr),
+    ] {
+      let Some(output) = output.as_deref().filter(|output| !output.is_empty())
+      else {
+        continue;
+      };
+
+      let shown = truncate(output, MAX_REPORT_OUTPUT_BYTES);
+
+      lines.push(format!("{name}:"));
+      lines.push(shown.trim_end().to_owned());
+
+      if shown.len() < output.len() {
+        lines.push(format!("... ({} more bytes)", output.len() - shown.len()));
+      }
+    }
+
+    lines.join("\n")
+  }
+
+  /// The signal that terminated the program, if it was killed by one.
+  pub fn signal(&self) -> Option<Signal> {
+    self.exit_signal.map(Signal::from)
+  }
+
+  /// Whether the instance reported that any output was truncated, through
+  /// flags like `stdout_truncated` that some judge0 deployments add. Output
+  /// that was cut short shouldn't be compared against the expected output.
+  pub fn is_output_truncated(&self) -> bool {
+    self.extra.iter().any(|(name, value)| {
+      name.ends_with("_truncated") && value.as_bool() == Some(true)
+    })
+  }
+}
+
+/// Validate every submission in a batch against the limits and features of
+/// an instance, returning the index and violations of each invalid
+/// submission so it can be dropped before sending the batch.
+///
+/// ```rust
+/// use judge0_rs::{validate_batch, Client, Config};
+///
+/// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+///
+/// let config_info = client.get_config_info().await.unwrap();
+///
+/// for (index, violations) in validate_batch(&submissions, &config_info) {
+///   eprintln!("submission {index} is invalid: {}", violations.join(", "));
+/// }
+/// ```
+pub fn validate_batch(
+  submissions: &[Submission],
+  config_info: &ConfigInfo,
+) -> Vec<(usize, Vec<String>)> {
+  submissions
+    .iter()
+    .enumerate()
+    .map(|(index, submission)| (index, submission.validate(config_info)))
+    .filter(|(_, violations)| !violations.is_empty())
+    .collect()
+}
+
+/// The distinct statuses of a batch, deduplicated by identifier in order of
+/// first appearance, to see at a glance whether anything failed.
+/// Submissions without a status are skipped.
+///
+/// ```rust
+/// use judge0_rs::{batch_status_summary, Client, Config};
+///
+/// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+///
+/// let submissions = client.run_batch(submissions).await.unwrap();
+///
+/// for status in batch_status_summary(&submissions) {
+///   println!("{}", status.description);
+/// }
+/// ```
+pub fn batch_status_summary(submissions: &[Submission]) -> Vec<Status> {
+  let mut statuses = Vec::<Status>::new();
+
+  for status in submissions
+    .iter()
+    .filter_map(|submission| submission.status.as_ref())
+  {
+    if !statuses.iter().any(|seen| seen.id == status.id) {
+      statuses.push(status.clone());
+    }
+  }
+
+  statuses
+}
+
+/// Percent-encode a query string component, leaving only unreserved
+/// characters as is.
+pub(crate) fn encode_query_component(component: &str) -> String {
+  component
+    .bytes()
+    .map(|byte| {
+      if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+        char::from(byte).to_string()
+      } else {
+        format!("%{byte:02X}")
+      }
+    })
+    .collect()
+}
+
+/// Quote a shell argument, leaving it untouched if it's made only of
+/// characters the shell doesn't treat specially.
+fn quote_arg(arg: &str) -> String {
+  let safe = |c: char| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c);
+
+  if !arg.is_empty() && arg.chars().all(safe) {
+    arg.to_owned()
+  } else {
+    format!("'{}'", arg.replace('\'', r"'\''"))
+  }
+}
+
+/// Deserialize a decimal judge0 may send either as a number or, for times and
+/// limits, as a string like `"0.001"`.
+fn deserialize_decimal<'de, D: Deserializer<'de>>(
+  deserializer: D,
+) -> Result<Option<f64>, D::Error> {
+  #[derive(Deserialize)]
+  #[serde(untagged)]
+  enum Repr {
+    Number(f64),
+    String(String),
+  }
+
+  Option::<Repr>::deserialize(deserializer)?
+    .map(|repr| match repr {
+      Repr::Number(number) => Ok(number),
+      Repr::String(string) => string.parse().map_err(serde::de::Error::custom),
+    })
+    .transpose()
+}
+
+/// Deserialize a submission status given either as an object or, for some
+/// trimmed responses, as a bare identifier.
+fn deserialize_status<'de, D: Deserializer<'de>>(
+  deserializer: D,
+) -> Result<Option<Status>, D::Error> {
+  #[derive(Deserialize)]
+  #[serde(untagged)]
+  enum Repr {
+    Id(usize),
+    Status(Status),
+  }
+
+  Ok(
+    Option::<Repr>::deserialize(deserializer)?.map(|repr| match repr {
+      Repr::Id(id) => Status {
+        id,
+        ..Default::default()
+      },
+      Repr::Status(status) => status,
+    }),
+  )
+}
+
+/// Deserialize a timestamp given either in RFC 3339 format or, by some judge0
+/// versions, as a datetime without a timezone, which is assumed to be UTC.
+fn deserialize_timestamp<'de, D: Deserializer<'de>>(
+  deserializer: D,
+) -> Result<Option<DateTime<Utc>>, D::Error> {
+  Option::<String>::deserialize(deserializer)?
+    .map(|timestamp| {
+      if let Ok(datetime) = DateTime::parse_from_rfc3339(&timestamp) {
+        return Ok(datetime.with_timezone(&Utc));
+      }
+
+      let naive = timestamp.trim_end_matches(" UTC");
+
+      ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(naive, format).ok())
+        .map(|datetime| datetime.and_utc())
+        .ok_or_else(|| {
+          serde::de::Error::custom(format!("invalid timestamp: {timestamp}"))
+        })
+    })
+    .transpose()
+}
+
+/// A batch of submissions, as returned when fetching several submissions.
+#[derive(Debug, Deserialize)]
+pub(crate) struct Submissions {
+  pub(crate) submissions: Vec<Submission>,
+}
+
+/// A submission field to request when fetching submissions, so that only
+/// the needed fields are transferred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SubmissionField {
+  SourceCode,
+  LanguageId,
+  CompilerOptions,
+  CommandLineArguments,
+  Stdin,
+  ExpectedOutput,
+  CpuTimeLimit,
+  CpuExtraTime,
+  WallTimeLimit,
+  MemoryLimit,
+  StackLimit,
+  MaxProcessesAndOrThreads,
+  EnablePerProcessAndThreadTimeLimit,
+  EnablePerProcessAndThreadMemoryLimit,
+  MaxFileSize,
+  RedirectStderrToStdout,
+  EnableNetwork,
+  NumberOfRuns,
+  AdditionalFiles,
+  CallbackUrl,
+  Queue,
+  Stdout,
+  Stderr,
+  CompileOutput,
+  Message,
+  ExitCode,
+  ExitSignal,
+  Status,
+  CreatedAt,
+  FinishedAt,
+  Token,
+  Time,
+  WallTime,
+  Memory,
+}
+
+impl SubmissionField {
+  /// The field name used by the judge0 API.
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::SourceCode => "source_code",
+      Self::LanguageId => "language_id",
+      Self::CompilerOptions => "compiler_options",
+      Self::CommandLineArguments => "command_line_arguments",
+      Self::Stdin => "stdin",
+      Self::ExpectedOutput => "expected_output",
+      Self::CpuTimeLimit => "cpu_time_limit",
+      Self::CpuExtraTime => "cpu_extra_time",
+      Self::WallTimeLimit => "wall_time_limit",
+      Self::MemoryLimit => "memory_limit",
+      Self::StackLimit => "stack_limit",
+      Self::MaxProcessesAndOrThreads => "max_processes_and_or_threads",
+      Self::EnablePerProcessAndThreadTimeLimit => {
+        "enable_per_process_and_thread_time_limit"
+      }
+      Self::EnablePerProcessAndThreadMemoryLimit => {
+        "enable_per_process_and_thread_memory_limit"
+      }
+      Self::MaxFileSize => "max_file_size",
+      Self::RedirectStderrToStdout => "redirect_stderr_to_stdout",
+      Self::EnableNetwork => "enable_network",
+      Self::NumberOfRuns => "number_of_runs",
+      Self::AdditionalFiles => "additional_files",
+      Self::CallbackUrl => "callback_url",
+      Self::Queue => "queue",
+      Self::Stdout => "stdout",
+      Self::Stderr => "stderr",
+      Self::CompileOutput => "compile_output",
+      Self::Message => "message",
+      Self::ExitCode => "exit_code",
+      Self::ExitSignal => "exit_signal",
+      Self::Status => "status",
+      Self::CreatedAt => "created_at",
+      Self::FinishedAt => "finished_at",
+      Self::Token => "token",
+      Self::Time => "time",
+      Self::WallTime => "wall_time",
+      Self::Memory => "memory",
+    }
+  }
+
+  /// The `fields` query parameter value for the given fields, `*` for all
+  /// fields.
+  pub(crate) fn query(fields: Option<&[Self]>) -> String {
+    match fields {
+      Some(fields) => fields
+        .iter()
+        .map(|field| field.as_str())
+        .collect::<Vec<&str>>()
+        .join(","),
+      None => "*".into(),
+    }
+  }
+}
+
+impl Display for SubmissionField {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+/// A submission fetched with only some fields, returned by
+/// `Client::get_submission_partial`, which only exposes the fields that were
+/// requested so that unfetched fields can't be mistaken for `null` ones.
+#[derive(Debug, Clone)]
+pub struct PartialSubmission {
+  fields: Vec<SubmissionField>,
+  values: HashMap<SubmissionField, Value>,
+}
+
+impl PartialSubmission {
+  /// Keep the requested `fields` of a fetched, decoded submission.
+  pub(crate) fn new(
+    submission: &Submission,
+    fields: &[SubmissionField],
+  ) -> Result<Self> {
+    let mut all = match serde_json::to_value(submission)? {
+      Value::Object(all) => all,
+      _ => serde_json::Map::new(),
+    };
+
+    Ok(Self {
+      fields: fields.to_vec(),
+      values: fields
+        .iter()
+        .map(|field| (*field, all.remove(field.as_str()).unwrap_or_default()))
+        .collect(),
+    })
+  }
+
+  /// The fields that were requested.
+  pub fn fields(&self) -> &[SubmissionField] {
+    &self.fields
+  }
+
+  /// The value of a requested field, or `None` if it was `null`. Fails with
+  /// `Error::FieldNotFetched` if the field wasn't requested.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config, Status, SubmissionField};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let submission = client
+  ///   .get_submission_partial("a", &[SubmissionField::Stdout, SubmissionField::Status])
+  ///   .await
+  ///   .unwrap();
+  ///
+  /// let stdout = submission.get::<String>(SubmissionField::Stdout).unwrap();
+  /// let status = submission.get::<Status>(SubmissionField::Status).unwrap();
+  /// ```
+  pub fn get<T: DeserializeOwned>(
+    &self,
+    field: SubmissionField,
+  ) -> Result<Option<T>> {
+    let value = self
+      .values
+      .get(&field)
+      .ok_or_else(|| Error::FieldNotFetched(field.as_str().into()))?;
+
+    Ok(serde_json::from_value(value.clone())?)
+  }
+}
+
+/// Timing of a submission from creation to a terminal status.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunMetrics {
+  /// Time the submission spent waiting for a worker, i.e. the time between
+  /// `created_at` and `finished_at` not spent executing.
+  pub queue_time: Option<Duration>,
+  /// Wall time the program ran for.
+  pub execution_time: Option<Duration>,
+  /// Time measured by the client, including network round trips and polling.
+  pub total_wall: Duration,
+}
+
+impl RunMetrics {
+  /// Derive the metrics of a finished submission, given the total time
+  /// measured by the client.
+  pub fn from_submission(
+    submission: &Submission,
+    total_wall: Duration,
+  ) -> Self {
+    let execution_time = submission.wall_clock_time();
+
+    let queue_time = submission
+      .created_at
+      .zip(submission.finished_at)
+      .and_then(|(created_at, finished_at)| {
+        (finished_at - created_at).to_std().ok()
+      })
+      .map(|server_time| {
+        server_time.saturating_sub(execution_time.unwrap_or_default())
+      });
+
+    Self {
+      queue_time,
+      execution_time,
+      total_wall,
+    }
+  }
+}
+
+/// The result of creating a submission, which depends on whether the
+/// instance was asked to wait for it to finish.
+#[derive(Debug, Clone)]
+pub enum CreateResponse {
+  /// The submission was queued, and its result can be fetched by token.
+  Pending(Token),
+  /// The instance waited for the submission and returned its result.
+  Completed(Box<Submission>),
+}
+
+impl CreateResponse {
+  /// The token of the created submission.
+  pub fn token(&self) -> &str {
+    match self {
+      Self::Pending(token) => &token.token,
+      Self::Completed(submission) => {
+        submission.token.as_deref().unwrap_or_default()
+      }
+    }
+  }
+}
+
+/// Unique submission token returned when creating a submission.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Token {
+  pub token: String,
+}
+
+/// The result of creating one submission in a batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BatchToken {
+  /// The submission was created.
+  Token(Token),
+  /// The submission was rejected, with the errors for each invalid field.
+  Error(HashMap<String, Value>),
+}
+
+/// The response to a batch submission, with one entry per submission in the
+/// order they were sent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchSubmissionResponse(pub Vec<BatchToken>);
+
+impl BatchSubmissionResponse {
+  /// The tokens of all created submissions, failing with `Error::Rejected`
+  /// if any submission in the batch was rejected.
+  pub fn tokens(&self) -> Result<Vec<String>> {
+    self
+      .0
+      .iter()
+      .map(|item| match item {
+        BatchToken::Token(token) => Ok(token.token.clone()),
+        BatchToken::Error(errors) => Err(Error::Rejected(Value::Object(
+          errors.clone().into_iter().collect(),
+        ))),
+      })
+      .collect()
+  }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SubmissionPage {
+  /// Submissions on this page.
+  pub submissions: Vec<Submission>,
+
+  /// Pagination information for the listing.
+  pub meta: Pagination,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Pagination {
+  pub current_page: usize,
+  pub next_page: Option<usize>,
+  pub prev_page: Option<usize>,
+  pub total_pages: usize,
+  pub total_count: usize,
+
+  /// Cursor of the next page, for deployments that use cursor-based
+  /// pagination instead of page numbers.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub next: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TestCase {
+  /// Standard input for the program.
+  pub stdin: Option<String>,
+
+  /// Expected output of the program for the given input.
+  pub expected_output: Option<String>,
+}
+
+/// A test case worth a number of points, for `Client::grade_weighted`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct WeightedCase {
+  /// Standard input for the program.
+  pub stdin: Option<String>,
+
+  /// Expected output of the program for the given input.
+  pub expected_output: Option<String>,
+
+  /// Points awarded if the program's output is accepted.
+  pub points: u32,
+}
+
+/// Points earned by a source across weighted test cases.
+#[derive(Debug, Clone)]
+pub struct ScoreResult {
+  /// Points earned across all accepted test cases.
+  pub earned: u32,
+
+  /// Points available across all test cases.
+  pub total: u32,
+
+  /// Each finished submission paired with the points it earned, in the same
+  /// order as the test cases.
+  pub per_case: Vec<(Submission, u32)>,
+}
+
+impl TestCase {
+  /// Read a test case from a JSON file like
+  /// `{ "stdin": "...", "expected_output": "..." }`.
+  ///
+  /// ```rust
+  /// use judge0_rs::TestCase;
+  ///
+  /// let case = TestCase::from_file("tests/cases/hello.json").unwrap();
+  /// ```
+  pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+    Ok(serde_json::from_slice(&fs::read(path)?)?)
+  }
+
+  /// Read every `.json` file in `dir` as a test case, in file name order.
+  ///
+  /// ```rust
+  /// use judge0_rs::{Client, Config, TestCase};
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let results = client
+  ///   .grade(
+  ///     "print(input())".into(),
+  ///     71,
+  ///     TestCase::many_from_dir("tests/cases").unwrap(),
+  ///   )
+  ///   .await
+  ///   .unwrap();
+  /// ```
+  pub fn many_from_dir(dir: impl AsRef<Path>) -> Result<Vec<Self>> {
+    let mut paths = fs::read_dir(dir)?
+      .map(|entry| entry.map(|entry| entry.path()))
+      .collect::<Result<Vec<PathBuf>, io::Error>>()?;
+
+    paths.retain(|path| {
+      path.is_file() && path.extension().is_some_and(|ext| ext == "json")
+    });
+
+    paths.sort();
+
+    paths.iter().map(Self::from_file).collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn compatibility() {
+    for (version, compatible) in [
+      ("1.5.0", true),
+      ("v1.13.1", true),
+      ("1.14.0-beta", true),
+      ("1", true),
+      ("0.9.0", false),
+      ("2.0.0", false),
+      ("latest", false),
+      ("1.x", false),
+    ] {
+      assert_eq!(
+        Compatibility::new(version).compatible,
+        compatible,
+        "{version}"
+      );
+    }
+  }
+
+  #[test]
+  fn language_is_archived() {
+    let language = serde_json::from_str::<Language>(
+      r#"{ "id": 45, "name": "Assembly (NASM 2.14.02)" }"#,
+    )
+    .unwrap();
+
+    assert_eq!(language.is_archived, None);
+    assert!(!language.is_archived());
+
+    let language = serde_json::from_str::<Language>(
+      r#"{ "id": 1, "name": "Bash (4.4)", "is_archived": true }"#,
+    )
+    .unwrap();
+
+    assert!(language.is_archived());
+  }
+
+  #[test]
+  fn validate_base64() {
+    let submission = Submission {
+      source_code: "cHJpbnQo\nImhlbGxvIik=".into(),
+      stdin: Some("d29ybGQ=".into()),
+      additional_files: Some("UEsFBgAAAAAAAAAAAAAAAAAAAAAAAA==".into()),
+      ..Default::default()
+    };
+
+    let config = Config::default();
+
+    let url_safe = Config {
+      base64_url_safe: true,
+      ..Default::default()
+    };
+
+    submission.validate_base64(&config).unwrap();
+    submission.validate_base64(&url_safe).unwrap();
+
+    assert!(matches!(
+      Submission {
+        source_code: "print(\"hello\")".into(),
+        ..submission.clone()
+      }
+      .validate_base64(&config),
+      Err(Error::Base64(field)) if field == "source_code"
+    ));
+
+    assert!(matches!(
+      Submission {
+        expected_output: Some("aGVsbG8=!".into()),
+        ..submission.clone()
+      }
+      .validate_base64(&config),
+      Err(Error::Base64(field)) if field == "expected_output"
+    ));
+
+    let standard_only = Submission {
+      stdin: Some("Pz8+Pg==".into()),
+      ..submission.clone()
+    };
+
+    standard_only.validate_base64(&config).unwrap();
+
+    assert!(matches!(
+      standard_only.validate_base64(&url_safe),
+      Err(Error::Base64(field)) if field == "stdin"
+    ));
+
+    let url_safe_only = Submission {
+      stdin: Some("Pz8-Pg==".into()),
+      ..submission
+    };
+
+    url_safe_only.validate_base64(&url_safe).unwrap();
+
+    assert!(matches!(
+      url_safe_only.validate_base64(&config),
+      Err(Error::Base64(field)) if field == "stdin"
+    ));
+  }
+
+  #[test]
+  fn harden() {
+    let mut submission = Submission {
+      enable_network: Some(true),
+      max_file_size: Some(1 << 20),
+      ..Default::default()
+    };
+
+    submission.harden();
+
+    assert_eq!(submission.max_file_size, Some(1024));
+    assert_eq!(submission.max_processes_and_or_threads, Some(16));
+    assert_eq!(submission.enable_network, Some(false));
+    assert_eq!(
+      submission.enable_per_process_and_thread_time_limit,
+      Some(true)
+    );
+    assert_eq!(
+      submission.enable_per_process_and_thread_memory_limit,
+      Some(true)
+    );
+  }
+
+  #[test]
+  fn shareable_round_trip() {
+    let mut submission = Submission {
+      source_code: "print(input())".into(),
+      language_id: 71,
+      cpu_time_limit: Some(2.0),
+      memory_limit: Some(128000.0),
+      stdout: Some("hello\n".into()),
+      token: Some("a".into()),
+      ..Default::default()
+    };
+
+    submission
+      .set_stdin_from_reader(&[0, 159, 146, 150][..])
+      .unwrap();
+
+    let blob = submission.to_shareable();
+
+    assert!(blob
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || "-_=".contains(c)));
+
+    let restored = Submission::from_shareable(&blob).unwrap();
+
+    assert_eq!(restored.source_code, submission.source_code);
+    assert_eq!(restored.language_id, 71);
+    assert_eq!(restored.cpu_time_limit, Some(2.0));
+    assert_eq!(restored.memory_limit, Some(128000.0));
+    assert_eq!(restored.stdin, submission.stdin);
+    assert!(restored.stdin_base64_encoded);
+    assert_eq!(restored.stdout, None);
+    assert_eq!(restored.token, None);
+
+    assert!(matches!(
+      Submission::from_shareable("not base64!"),
+      Err(Error::Base64(_))
+    ));
+  }
+
+  #[test]
+  fn batch_status_summary() {
+    let submission = |id, description: &str| Submission {
+      status: Some(Status {
+        id,
+        description: description.into(),
+      }),
+      ..Default::default()
+    };
+
+    let submissions = [
+      submission(3, "Accepted"),
+      submission(4, "Wrong Answer"),
+      Submission::default(),
+      submission(3, "Accepted"),
+      submission(4, "Wrong Answer"),
+    ];
+
+    assert_eq!(
+      super::batch_status_summary(&submissions),
+      [
+        Status {
+          id: 3,
+          description: "Accepted".into(),
+        },
+        Status {
+          id: 4,
+          description: "Wrong Answer".into(),
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn auto_wall_time() {
+    let mut submission = Submission {
+      cpu_time_limit: Some(1.5),
+      ..Default::default()
+    };
+
+    submission.auto_wall_time(4.0);
+
+    assert_eq!(submission.wall_time_limit, Some(6.0));
+
+    let mut submission = Submission::default();
+
+    submission.auto_wall_time(4.0);
+
+    assert_eq!(submission.wall_time_limit, None);
+  }
+
+  #[test]
+  fn report() {
+    let submission = Submission {
+      status: Some(Status {
+        id: 6,
+        description: "Compilation Error".into(),
+      }),
+      time: Some(0.25),
+      memory: Some(3200.0),
+      compile_output: Some("main.c:1: error: expected ';'\n".into()),
+      stdout: Some("x".repeat(MAX_REPORT_OUTPUT_BYTES + 10)),
+      stderr: Some(String::new()),
+      ..Default::default()
+    };
+
+    let report = submission.report();
+
+    assert!(report.starts_with("Status: Compilation Error (6)\n"));
+    assert!(report.contains("Time: 0.25 s\n"));
+    assert!(report.contains("Memory: 3200 KB\n"));
+    assert!(report.contains("Compile output:\nmain.c:1: error: expected ';'\n"));
+    assert!(report.ends_with("... (10 more bytes)"));
+    assert!(!report.contains("Stderr"));
+    assert!(!report.contains("Exit code"));
+  }
+
+  #[test]
+  fn stdout_lines() {
+    let submission = Submission {
+      stdout: Some("foo\nbar\r\n\nbaz\n".into()),
+      ..Default::default()
+    };
+
+    assert_eq!(
+      submission.stdout_lines().unwrap().collect::<Vec<_>>(),
+      ["foo", "bar", "", "baz"]
+    );
+
+    assert!(Submission::default().stdout_lines().is_none());
+  }
+
+  #[test]
+  fn diff_serialize() {
+    let template = Submission {
+      source_code: "print(input())".into(),
+      language_id: 71,
+      cpu_time_limit: Some(2.0),
+      stdin: Some("a".into()),
+      ..Default::default()
+    };
+
+    let submission = Submission {
+      stdin: Some("b".into()),
+      ..template.clone()
+    };
+
+    assert_eq!(
+      serde_json::from_str::<Value>(
+        &submission.diff_serialize(&template).unwrap()
+      )
+      .unwrap(),
+      serde_json::json!({
+        "source_code": "print(input())",
+        "language_id": 71,
+        "stdin": "b",
+      })
+    );
+  }
+
+  #[test]
+  fn signal() {
+    let signal = |exit_signal| {
+      Submission {
+        exit_signal,
+        ..Default::default()
+      }
+      .signal()
+    };
+
+    assert_eq!(signal(Some(11)), Some(Signal::Segv));
+    assert_eq!(signal(Some(8)), Some(Signal::FloatingPoint));
+    assert_eq!(signal(Some(9)), Some(Signal::Killed));
+    assert_eq!(signal(Some(64)), Some(Signal::Other(64)));
+    assert_eq!(signal(None), None);
+  }
+
+  #[test]
+  fn status_category() {
+    let status = |id| Status {
+      id,
+      ..Default::default()
+    };
+
+    assert_eq!(status(1).category(), StatusCategory::Queued);
+    assert_eq!(status(2).category(), StatusCategory::Queued);
+    assert_eq!(status(3).category(), StatusCategory::Success);
+    assert_eq!(status(4).category(), StatusCategory::Failure);
+    assert_eq!(status(6).category(), StatusCategory::Failure);
+    assert_eq!(status(13).category(), StatusCategory::Failure);
+  }
+
+  #[test]
+  fn submission_durations() {
+    let submission = Submission {
+      time: Some(0.001),
+      wall_time: Some(1.5),
+      ..Default::default()
+    };
+
+    assert_eq!(submission.cpu_time(), Some(Duration::from_millis(1)));
+
+    assert_eq!(
+      submission.wall_clock_time(),
+      Some(Duration::from_millis(1500))
+    );
+
+    assert_eq!(Submission::default().cpu_time(), None);
+    assert_eq!(Submission::default().wall_clock_time(), None);
+  }
+
+  #[test]
+  fn submission_request_json() {
+    let submission = Submission {
+      source_code: "print(1)".into(),
+      language_id: 71,
+      stdin: Some("input".into()),
+      ..Default::default()
+    };
+
+    assert_eq!(
+      submission.to_request_json(&Config::default()).unwrap(),
+      r#"{"language_id":71,"source_code":"print(1)","stdin":"input"}"#
+    );
+
+    assert_eq!(
+      submission
+        .to_request_json(&Config {
+          base64_encoded: true,
+          ..Default::default()
+        })
+        .unwrap(),
+      r#"{"language_id":71,"source_code":"cHJpbnQoMSk=","stdin":"aW5wdXQ="}"#
+    );
+  }
+
+  #[test]
+  fn submission_queue_serialized_when_present() {
+    let submission = Submission::default();
+
+    assert!(serde_json::to_value(&submission)
+      .unwrap()
+      .get("queue")
+      .is_none());
+
+    let submission = Submission {
+      queue: Some("heavy".into()),
+      ..Default::default()
+    };
+
+    assert_eq!(serde_json::to_value(&submission).unwrap()["queue"], "heavy");
+  }
+
+  #[test]
+  fn submission_status_bare_id() {
+    let submission =
+      serde_json::from_str::<Submission>(r#"{ "status": 3 }"#).unwrap();
+
+    assert_eq!(
+      submission.status,
+      Some(Status {
+        id: 3,
+        description: String::new(),
+      })
+    );
+  }
+
+  #[test]
+  fn submission_status_object() {
+    let submission = serde_json::from_str::<Submission>(
+      r#"{ "status": { "id": 3, "description": "Accepted" } }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+      submission.status,
+      Some(Status {
+        id: 3,
+        description: "Accepted".into(),
+      })
+    );
+
+    let submission =
+      serde_json::from_str::<Submission>(r#"{ "status": null }"#).unwrap();
+
+    assert_eq!(submission.status, None);
+  }
+
+  #[test]
+  fn submission_times_as_strings() {
+    let submission = serde_json::from_str::<Submission>(
+      r#"{ "time": "0.001", "wall_time": 0.5, "memory": 380, "cpu_time_limit": "5.0" }"#,
+    )
+    .unwrap();
+
+    assert_eq!(submission.time, Some(0.001));
+    assert_eq!(submission.wall_time, Some(0.5));
+    assert_eq!(submission.memory, Some(380.0));
+    assert_eq!(submission.cpu_time_limit, Some(5.0));
+  }
+
+  #[test]
+  fn submission_extra_fields() {
+    let submission = serde_json::from_str::<Submission>(
+      r#"{
+        "token": "a",
+        "time": "0.001",
+        "status": { "id": 3, "description": "Accepted" },
+        "compile_output_truncated": false,
+        "post_execution_filesystem": "UEsDBA=="
+      }"#,
+    )
+    .unwrap();
+
+    assert_eq!(submission.token.as_deref(), Some("a"));
+    assert_eq!(submission.time, Some(0.001));
+    assert_eq!(submission.status.as_ref().unwrap().id, 3);
+
+    assert_eq!(submission.extra.len(), 2);
+    assert_eq!(submission.extra["compile_output_truncated"], false);
+    assert_eq!(submission.extra["post_execution_filesystem"], "UEsDBA==");
+
+    let value = serde_json::to_value(&submission).unwrap();
+
+    assert_eq!(value["post_execution_filesystem"], "UEsDBA==");
+  }
+
+  #[test]
+  fn submission_limits_exceeded() {
+    let submission = Submission {
+      time: Some(0.5),
+      memory: Some(2048.0),
+      ..Default::default()
+    };
+
+    assert_eq!(submission.memory_exceeded(1024.0), Some(true));
+    assert_eq!(submission.memory_exceeded(4096.0), Some(false));
+
+    assert_eq!(submission.time_exceeded(0.25), Some(true));
+    assert_eq!(submission.time_exceeded(1.0), Some(false));
+
+    assert_eq!(Submission::default().memory_exceeded(1024.0), None);
+    assert_eq!(Submission::default().time_exceeded(1.0), None);
+  }
+
+  #[test]
+  fn submission_set_args() {
+    let mut submission = Submission::default();
+
+    submission.set_args(&["-n", "10", "input.txt"]);
+
+    assert_eq!(
+      submission.command_line_arguments.as_deref(),
+      Some("-n 10 input.txt")
+    );
+
+    submission.set_args(&["hello world", "it's", "", "$HOME"]);
+
+    assert_eq!(
+      submission.command_line_arguments.as_deref(),
+      Some(r"'hello world' 'it'\''s' '' '$HOME'")
+    );
+  }
+
+  #[test]
+  fn submission_progress() {
+    let submission = |id| Submission {
+      status: Some(Status {
+        id,
+        ..Default::default()
+      }),
+      ..Default::default()
+    };
+
+    assert!(submission(1).is_queued());
+    assert!(!submission(1).is_processing());
+    assert!(!submission(1).is_finished());
+
+    assert!(!submission(2).is_queued());
+    assert!(submission(2).is_processing());
+    assert!(!submission(2).is_finished());
+
+    for id in [3, 4, 5, 6, 11, 13, 14] {
+      assert!(!submission(id).is_queued());
+      assert!(!submission(id).is_processing());
+      assert!(submission(id).is_finished());
+    }
+
+    let submission = Submission::default();
+
+    assert!(!submission.is_queued());
+    assert!(!submission.is_processing());
+    assert!(!submission.is_finished());
+  }
+
+  #[test]
+  fn metadata_not_serialized() {
+    let submission = Submission {
+      source_code: "print(1)".into(),
+      language_id: 71,
+      metadata: HashMap::from([("job_id".into(), "42".into())]),
+      ..Default::default()
+    };
+
+    let clone = submission.clone();
+
+    assert_eq!(clone.metadata["job_id"], "42");
+
+    let json = serde_json::to_value(&clone).unwrap();
+
+    assert!(json.get("metadata").is_none());
+    assert!(json.get("job_id").is_none());
+  }
+
+  #[test]
+  fn is_output_truncated() {
+    let submission = serde_json::from_str::<Submission>(
+      r#"{ "stdout": "aaaa", "stdout_truncated": true }"#,
+    )
+    .unwrap();
+
+    assert!(submission.is_output_truncated());
+
+    let submission = serde_json::from_str::<Submission>(
+      r#"{ "stdout": "aaaa", "compile_output_truncated": false }"#,
+    )
+    .unwrap();
+
+    assert!(!submission.is_output_truncated());
+
+    assert!(!Submission::default().is_output_truncated());
+  }
+
+  #[test]
+  fn language_version() {
+    let language = |name: &str| Language {
+      id: 1,
+      name: name.into(),
+      is_archived: None,
+      source_file: None,
+      compile_cmd: None,
+      run_cmd: None,
+    };
+
+    let python = language("Python (3.8.1)");
+
+    assert_eq!(python.version().as_deref(), Some("3.8.1"));
+    assert_eq!(python.base_name(), "Python");
+
+    let assembly = language("Assembly (NASM 2.14.02)");
+
+    assert_eq!(assembly.version().as_deref(), Some("NASM 2.14.02"));
+    assert_eq!(assembly.base_name(), "Assembly");
+
+    let text = language("Plain Text");
+
+    assert_eq!(text.version(), None);
+    assert_eq!(text.base_name(), "Plain Text");
+  }
+
+  #[test]
+  fn set_stdin_from_reader() {
+    let input = [0, 159, 146, 150, b'\n'];
+
+    let mut submission = Submission {
+      source_code: "print(input())".into(),
+      language_id: 71,
+      ..Default::default()
+    };
+
+    submission.set_stdin_from_reader(&input[..]).unwrap();
+
+    assert_eq!(submission.stdin, Some(STANDARD.encode(input)));
+    assert_eq!(submission.base64_encoded_override, Some(true));
+
+    let body = submission.request_body(true, &STANDARD).unwrap();
+
+    assert_eq!(body["stdin"], STANDARD.encode(input));
+    assert_eq!(body["source_code"], STANDARD.encode("print(input())"));
+
+    assert!(matches!(
+      submission.request_body(false, &STANDARD),
+      Err(Error::BinaryStdin)
+    ));
+
+    assert!(matches!(
+      submission.diff_serialize(&Submission::default()),
+      Err(Error::BinaryStdin)
+    ));
+  }
+
+  #[test]
+  fn base64_alphabets() {
+    let text = "??>>";
+
+    for (engine, encoded) in [(&STANDARD, "Pz8+Pg=="), (&URL_SAFE, "Pz8-Pg==")]
+    {
+      let mut submission = Submission {
+        source_code: text.into(),
+        language_id: 71,
+        ..Default::default()
+      };
+
+      submission.set_stdin_from_reader(text.as_bytes()).unwrap();
+
+      let body = submission.request_body(true, engine).unwrap();
+
+      assert_eq!(body["source_code"], encoded);
+      assert_eq!(body["stdin"], encoded);
+
+      let mut fetched = Submission {
+        source_code: encoded.into(),
+        stdout: Some(encoded.into()),
+        ..Default::default()
+      };
+
+      fetched.decode(engine).unwrap();
+
+      assert_eq!(fetched.source_code, text);
+      assert_eq!(fetched.stdout.as_deref(), Some(text));
+    }
+  }
+
+  #[test]
+  fn flexible_timestamps() {
+    let expected = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+
+    for timestamp in [
+      "2021-01-01T00:00:00.000Z",
+      "2021-01-01T00:00:00+00:00",
+      "2021-01-01 00:00:00",
+      "2021-01-01 00:00:00 UTC",
+      "2021-01-01T00:00:00.000",
+    ] {
+      let submission = serde_json::from_str::<Submission>(&format!(
+        r#"{{ "created_at": "{timestamp}", "finished_at": null }}"#
+      ))
+      .unwrap();
+
+      assert_eq!(submission.created_at, Some(expected), "{timestamp}");
+      assert_eq!(submission.finished_at, None);
+    }
+
+    assert!(serde_json::from_str::<Submission>(
+      r#"{ "created_at": "yesterday" }"#
+    )
+    .is_err());
+  }
+
+  #[test]
+  fn test_cases_from_dir() {
+    let dir = std::env::temp_dir()
+      .join(format!("judge0-rs-test-cases-{}", std::process::id()));
+
+    fs::create_dir_all(&dir).unwrap();
+
+    fs::write(
+      dir.join("b.json"),
+      r#"{ "stdin": "bar", "expected_output": "bar" }"#,
+    )
+    .unwrap();
+
+    fs::write(dir.join("a.json"), r#"{ "stdin": "foo" }"#).unwrap();
+
+    fs::write(dir.join("notes.txt"), "not a test case").unwrap();
+
+    assert_eq!(
+      TestCase::from_file(dir.join("b.json")).unwrap(),
+      TestCase {
+        stdin: Some("bar".into()),
+        expected_output: Some("bar".into()),
+      }
+    );
+
+    assert_eq!(
+      TestCase::many_from_dir(&dir).unwrap(),
+      vec![
+        TestCase {
+          stdin: Some("foo".into()),
+          expected_output: None,
+        },
+        TestCase {
+          stdin: Some("bar".into()),
+          expected_output: Some("bar".into()),
+        },
+      ]
+    );
+
+    fs::write(dir.join("c.json"), "not json").unwrap();
+
+    assert!(matches!(
+      TestCase::many_from_dir(&dir),
+      Err(Error::Serde(_))
+    ));
+
+    assert!(matches!(
+      TestCase::from_file(dir.join("missing.json")),
+      Err(Error::Io(_))
+    ));
+
+    fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn set_callback_with_secret() {
+    let mut submission = Submission::default();
+
+    submission.set_callback_with_secret("https://example.com/callback", "abc");
+
+    assert_eq!(
+      submission.callback_url.as_deref(),
+      Some("https://example.com/callback?secret=abc")
+    );
+
+    submission
+      .set_callback_with_secret("https://example.com/callback?job=1", "a b&c");
+
+    assert_eq!(
+      submission.callback_url.as_deref(),
+      Some("https://example.com/callback?job=1&secret=a%20b%26c")
+    );
+  }
+
+  #[test]
+  fn submission_field_query() {
+    assert_eq!(SubmissionField::query(None), "*");
+
+    assert_eq!(
+      SubmissionField::query(Some(&[
+        SubmissionField::SourceCode,
+        SubmissionField::MaxProcessesAndOrThreads,
+        SubmissionField::Status,
+      ])),
+      "source_code,max_processes_and_or_threads,status"
+    );
+  }
+
+  #[test]
+  fn validate_batch_limits() {
+    let config_info = ConfigInfo {
+      enable_command_line_arguments: true,
+      max_cpu_time_limit: 15.0,
+      max_memory_limit: 512000.0,
+      max_number_of_runs: 20,
+      ..Default::default()
+    };
+
+    let submissions = [
+      Submission {
+        cpu_time_limit: Some(5.0),
+        command_line_arguments: Some("--fast".into()),
+        ..Default::default()
+      },
+      Submission {
+        cpu_time_limit: Some(20.0),
+        compiler_options: Some("-O2".into()),
+        ..Default::default()
+      },
+      Submission {
+        number_of_runs: Some(20),
+        ..Default::default()
+      },
+    ];
+
+    assert_eq!(
+      validate_batch(&submissions, &config_info),
+      vec![(
+        1,
+        vec![
+          "compiler_options is not enabled on this instance".into(),
+          "cpu_time_limit 20 exceeds the maximum of 15".into(),
+        ]
+      )]
+    );
+  }
+
+  #[test]
+  fn run_metrics() {
+    let submission = serde_json::from_str::<Submission>(
+      r#"{
+        "created_at": "2021-01-01T00:00:00.000Z",
+        "finished_at": "2021-01-01T00:00:02.500Z",
+        "wall_time": "0.5",
+        "status": { "id": 3, "description": "Accepted" }
+      }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+      RunMetrics::from_submission(&submission, Duration::from_secs(3)),
+      RunMetrics {
+        queue_time: Some(Duration::from_secs(2)),
+        execution_time: Some(Duration::from_millis(500)),
+        total_wall: Duration::from_secs(3),
+      }
+    );
+
+    assert_eq!(
+      RunMetrics::from_submission(&Submission::default(), Duration::ZERO),
+      RunMetrics {
+        queue_time: None,
+        execution_time: None,
+        total_wall: Duration::ZERO,
+      }
+    );
+  }
 }
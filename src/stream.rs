@@ -0,0 +1,159 @@
+use {
+  super::*,
+  futures_util::{stream, Stream},
+};
+
+enum Progress {
+  Create(Box<Submission>),
+  Poll { token: String, last: Option<usize> },
+  Done,
+}
+
+impl Client {
+  /// Create a submission and stream its status every time it changes, e.g.
+  /// In Queue, Processing and then Accepted, finishing once the submission
+  /// reaches a terminal status.
+  ///
+  /// ```rust
+  /// use {
+  ///   futures_util::StreamExt,
+  ///   judge0_rs::{Client, Config, Submission},
+  ///   std::time::Duration,
+  /// };
+  ///
+  /// let client = Client::new("http://localhost:2358").configure(Config::default()).unwrap();
+  ///
+  /// let submission = Submission {
+  ///   source_code: "print(Hello, world)".into(),
+  ///   language_id: 1,
+  ///   ..Submission::default()
+  /// };
+  ///
+  /// let mut progress = Box::pin(client.run_with_progress(submission, Duration::from_secs(1)));
+  ///
+  /// while let Some(status) = progress.next().await {
+  ///   println!("{}", status.unwrap().description);
+  /// }
+  /// ```
+  pub fn run_with_progress(
+    &self,
+    submission: Submission,
+    interval: Duration,
+  ) -> impl Stream<Item = Result<Status>> + '_ {
+    let progress = Progress::Create(Box::new(submission));
+
+    stream::try_unfold(progress, move |progress| async move {
+      let (token, last) = match progress {
+        Progress::Create(submission) => {
+          let result = self
+            .create_submission(Submission {
+              wait_override: Some(false),
+              ..*submission
+            })
+            .await?;
+
+          (result.token().to_owned(), None)
+        }
+        Progress::Poll { token, last } => {
+          sleep(self.poll_delay(interval)).await;
+          (token, last)
+        }
+        Progress::Done => return Ok(None),
+      };
+
+      loop {
+        let submission = self
+          .get_submission(&token, Some(&[SubmissionField::Status]))
+          .await?;
+
+        if let Some(status) = submission.status {
+          if last != Some(status.id) {
+            let next = if status.category() == StatusCategory::Queued {
+              Progress::Poll {
+                token,
+                last: Some(status.id),
+              }
+            } else {
+              Progress::Done
+            };
+
+            return Ok(Some((status, next)));
+          }
+        }
+
+        sleep(self.poll_delay(interval)).await;
+      }
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, futures_util::TryStreamExt, mockito::Server};
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn run_with_progress_ok() {
+    let mut server = Server::new_async().await;
+
+    let client = Client::new(&server.url());
+
+    let create = server
+      .mock("POST", "/submissions?base64_encoded=false&wait=false")
+      .with_status(201)
+      .with_header("content-type", "application/json")
+      .with_body(r#"{ "token": "a" }"#)
+      .create();
+
+    let mut polls = Vec::new();
+
+    for (id, description) in [
+      (1, "In Queue"),
+      (1, "In Queue"),
+      (2, "Processing"),
+      (2, "Processing"),
+      (3, "Accepted"),
+    ] {
+      polls.push(
+        server
+          .mock(
+            "GET",
+            "/submissions/a?base64_encoded=false&wait=false&fields=status",
+          )
+          .with_status(200)
+          .with_header("content-type", "application/json")
+          .with_body(format!(
+            r#"{{ "status": {{ "id": {id}, "description": "{description}" }} }}"#
+          ))
+          .expect(1)
+          .create(),
+      );
+    }
+
+    let statuses = client
+      .run_with_progress(
+        Submission {
+          source_code: r#"print("Hello, world!")"#.into(),
+          language_id: 71,
+          ..Default::default()
+        },
+        Duration::ZERO,
+      )
+      .try_collect::<Vec<Status>>()
+      .await
+      .unwrap();
+
+    assert_eq!(
+      statuses
+        .iter()
+        .map(|status| status.id)
+        .collect::<Vec<usize>>(),
+      vec![1, 2, 3]
+    );
+
+    create.assert();
+
+    for poll in polls {
+      poll.assert();
+    }
+  }
+}